@@ -0,0 +1,94 @@
+/// Generates the registry of solved days that is compiled into the main binary.
+/// Every `src/bin/DD.rs` and `src/bin/YYYY/DD.rs` is included as a module, so `all` and `time` can run the days in-process.
+use std::{env, fs, path::Path};
+
+/// Returns the day number and path of every `DD.rs` file in `dir`.
+fn find_days(dir: &Path) -> Vec<(u8, String)> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?;
+                    let day: u8 = stem.parse().ok()?;
+                    (stem.len() == 2 && (1..=25).contains(&day))
+                        .then(|| (day, path.to_string_lossy().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<(Option<u16>, u8, String)> = find_days(&bin_dir)
+        .into_iter()
+        .map(|(day, path)| (None, day, path))
+        .collect();
+
+    let year_dirs = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .filter_map(|path| {
+                    let name = path.file_name()?.to_str()?;
+                    let year: u16 = name.parse().ok()?;
+                    (name.len() == 4 && year >= 2015).then_some((year, path))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for (year, path) in year_dirs {
+        println!("cargo:rerun-if-changed={}", path.display());
+        days.extend(
+            find_days(&path)
+                .into_iter()
+                .map(|(day, path)| (Some(year), day, path)),
+        );
+    }
+
+    days.sort_unstable();
+
+    let module_name = |year: &Option<u16>, day: &u8| match year {
+        Some(year) => format!("day_{year}_{day:02}"),
+        None => format!("day_{day:02}"),
+    };
+
+    let mut registry = String::new();
+
+    for (year, day, path) in &days {
+        registry.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code, unused_imports, unused_variables)]\nmod {};\n",
+            module_name(year, day)
+        ));
+    }
+
+    registry
+        .push_str("\npub const DAYS: &[advent_of_code::template::runner::RegisteredDay] = &[\n");
+
+    for (year, day, _) in &days {
+        let year_expr = match year {
+            Some(year) => format!("advent_of_code::template::Year::new({year})"),
+            None => "None".into(),
+        };
+        registry.push_str(&format!(
+            "    advent_of_code::template::runner::RegisteredDay {{ year: {year_expr}, day: advent_of_code::day!({day}), run: {}::__run }},\n",
+            module_name(year, day)
+        ));
+    }
+
+    registry.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(out_path, registry).unwrap();
+}
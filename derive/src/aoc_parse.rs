@@ -0,0 +1,238 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Member};
+
+/// A piece of a pattern as written in the attribute.
+enum Part {
+    Literal(String),
+    Field(String),
+}
+
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let alternatives = match &input.data {
+        Data::Struct(data) => {
+            let pattern = pattern_attr(&input.attrs, name)?;
+            vec![alternative(quote!(Self), &data.fields, &pattern)?]
+        }
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let pattern = pattern_attr(&variant.attrs, &variant.ident)?;
+                let ident = &variant.ident;
+                alternative(quote!(Self::#ident), &variant.fields, &pattern)
+            })
+            .collect::<syn::Result<_>>()?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "`AocParse` can only be derived for structs and enums",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::advent_of_code::ParseError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                use ::advent_of_code::parse::{match_pattern, parse_field, PatternMismatch, PatternPart};
+
+                let mut mismatches = ::std::vec::Vec::new();
+                #( #alternatives )*
+                ::core::result::Result::Err(PatternMismatch::into_error(s, mismatches))
+            }
+        }
+    })
+}
+
+/// Code that returns the value built from `fields` if the line matches `pattern`.
+fn alternative(path: TokenStream, fields: &Fields, pattern: &LitStr) -> syn::Result<TokenStream> {
+    let parts = parse_pattern(pattern)?;
+
+    let members: Vec<(Member, &syn::Type)> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(i.into()),
+            };
+            (member, &field.ty)
+        })
+        .collect();
+
+    let mut pattern_parts = vec![];
+    let mut placeholders = vec![];
+
+    for (i, part) in parts.iter().enumerate() {
+        match part {
+            Part::Literal(literal) => pattern_parts.push(quote!(PatternPart::Literal(#literal))),
+            Part::Field(field) => {
+                if matches!(parts.get(i + 1), Some(Part::Field(_))) {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        "placeholders have to be separated by text",
+                    ));
+                }
+
+                let Some(index) = members.iter().position(|(m, _)| member_name(m) == *field) else {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        format!("no field `{field}`"),
+                    ));
+                };
+
+                if placeholders.contains(&index) {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        format!("`{{{field}}}` is used more than once"),
+                    ));
+                }
+
+                pattern_parts.push(quote!(PatternPart::Field));
+                placeholders.push(index);
+            }
+        }
+    }
+
+    if let Some((_, (member, _))) = members
+        .iter()
+        .enumerate()
+        .find(|(i, _)| !placeholders.contains(i))
+    {
+        return Err(syn::Error::new_spanned(
+            pattern,
+            format!("missing placeholder for field `{}`", member_name(member)),
+        ));
+    }
+
+    // parse fields in the order of the pattern, so the first error is the leftmost one.
+    let vars: Vec<Ident> = (0..placeholders.len())
+        .map(|i| format_ident!("__field_{}", i))
+        .collect();
+    let parses = placeholders
+        .iter()
+        .zip(&vars)
+        .enumerate()
+        .map(|(i, (index, var))| {
+            let (member, ty) = &members[*index];
+            let field = member_name(member);
+            quote!(let #var: #ty = parse_field(s, values[#i], #field)?;)
+        });
+
+    let inits = members.iter().enumerate().map(|(index, (member, _))| {
+        let var = &vars[placeholders.iter().position(|i| *i == index).unwrap()];
+        quote!(#member: #var)
+    });
+
+    let value = match fields {
+        Fields::Unit => path,
+        _ => quote!(#path { #( #inits ),* }),
+    };
+
+    let values = if vars.is_empty() {
+        quote!(_)
+    } else {
+        quote!(values)
+    };
+
+    Ok(quote! {
+        match match_pattern(s, &[ #( #pattern_parts ),* ]) {
+            ::core::result::Result::Ok(#values) => {
+                #( #parses )*
+                return ::core::result::Result::Ok(#value);
+            }
+            ::core::result::Result::Err(mismatch) => mismatches.push(mismatch),
+        }
+    })
+}
+
+/// The pattern of an `#[aoc_parse("...")]` attribute.
+fn pattern_attr(attrs: &[syn::Attribute], owner: &Ident) -> syn::Result<LitStr> {
+    let mut attrs = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("aoc_parse"));
+
+    let Some(attr) = attrs.next() else {
+        return Err(syn::Error::new_spanned(
+            owner,
+            "missing `#[aoc_parse(\"...\")]` attribute",
+        ));
+    };
+
+    if let Some(attr) = attrs.next() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "only one `#[aoc_parse(\"...\")]` attribute is allowed",
+        ));
+    }
+
+    attr.parse_args()
+}
+
+/// Split a pattern like `{x},{y}` into text and placeholders. `{{` and `}}` stand for literal braces.
+fn parse_pattern(pattern: &LitStr) -> syn::Result<Vec<Part>> {
+    let error = |message: &str| syn::Error::new(pattern.span(), message);
+
+    let value = pattern.value();
+    let mut chars = value.chars().peekable();
+    let mut parts = vec![];
+    let mut literal = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(error("unclosed `{` in pattern")),
+                    }
+                }
+
+                let field = field.trim().to_string();
+                if field.is_empty() {
+                    return Err(error("placeholders need a field name, e.g. `{x}`"));
+                }
+
+                if !literal.is_empty() {
+                    parts.push(Part::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(Part::Field(field));
+            }
+            '}' => {
+                return Err(error(
+                    "unmatched `}` in pattern, use `}}` for a literal brace",
+                ))
+            }
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        parts.push(Part::Literal(literal));
+    }
+
+    Ok(parts)
+}
+
+/// The name of a field as written in a placeholder: its identifier, or its index for tuple fields.
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
@@ -0,0 +1,99 @@
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, ExprLit, Fields, Lit, LitChar};
+
+pub fn derive(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`FromChar` can only be derived for enums",
+        ));
+    };
+
+    let mut variants = vec![];
+    let mut chars: Vec<LitChar> = vec![];
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`FromChar` variants cannot have fields",
+            ));
+        }
+
+        let c = variant_char(variant)?;
+        if let Some(duplicate) = chars.iter().find(|other| other.value() == c.value()) {
+            return Err(syn::Error::new_spanned(
+                &c,
+                format!("`{}` is already used by another variant", duplicate.value()),
+            ));
+        }
+
+        variants.push(&variant.ident);
+        chars.push(c);
+    }
+
+    let expected = chars
+        .iter()
+        .map(|c| format!("`{}`", c.value()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    // passed as an argument rather than as the format string, as the characters can be braces.
+    let expected = format!("expected one of {expected} for `{name}`");
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::TryFrom<char> for #name #ty_generics #where_clause {
+            type Error = ::advent_of_code::ParseError;
+
+            fn try_from(c: char) -> ::core::result::Result<Self, Self::Error> {
+                match c {
+                    #( #chars => ::core::result::Result::Ok(#name::#variants), )*
+                    c => ::core::result::Result::Err(::advent_of_code::ParseError::new(::std::format!("{}, found `{}`", #expected, c))),
+                }
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let c = match self {
+                    #( #name::#variants => #chars, )*
+                };
+                ::core::fmt::Write::write_char(f, c)
+            }
+        }
+    })
+}
+
+/// The character of a `#[char = '.']` attribute of a variant.
+fn variant_char(variant: &syn::Variant) -> syn::Result<LitChar> {
+    let mut attrs = variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("char"));
+
+    let Some(attr) = attrs.next() else {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "missing `#[char = '.']` attribute",
+        ));
+    };
+
+    if let Some(attr) = attrs.next() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "a variant maps to exactly one character",
+        ));
+    }
+
+    match &attr.meta.require_name_value()?.value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Char(c), ..
+        }) => Ok(c.clone()),
+        value => Err(syn::Error::new_spanned(
+            value,
+            "expected a character literal, e.g. `#[char = '.']`",
+        )),
+    }
+}
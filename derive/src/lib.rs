@@ -0,0 +1,32 @@
+//! Derive macros of the `advent_of_code` library. Use them through the re-exports of the library.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod aoc_parse;
+mod from_char;
+
+/// Maps the variants of a fieldless enum to the characters given by their `#[char = '.']` attributes.
+///
+/// Generates `TryFrom<char>`, failing with a `ParseError` that lists the expected characters, and
+/// `Display`, which writes the character of a variant.
+#[proc_macro_derive(FromChar, attributes(char))]
+pub fn derive_from_char(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_char::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates `FromStr` for a struct from a line pattern like `#[aoc_parse("{x},{y}")]`, or for an enum
+/// from one pattern per variant. Placeholders name fields, or their index for tuple fields, and are
+/// parsed with the `FromStr` of the field's type. `{{` and `}}` stand for literal braces.
+///
+/// Errors are `ParseError`s pointing at the column of the offending text.
+#[proc_macro_derive(AocParse, attributes(aoc_parse))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    aoc_parse::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -1,21 +1,15 @@
+use advent_of_code::{parse_lines, AocParse, ParseError};
+
 advent_of_code::solution!(1);
 
-#[derive(Debug)]
+#[derive(Debug, AocParse)]
 enum Rotation {
+    #[aoc_parse("L{0}")]
     Left(u16),
+    #[aoc_parse("R{0}")]
     Right(u16),
 }
 
-impl Rotation {
-    fn from(input: &str) -> Option<Rotation> {
-        match input.chars().next() {
-            Some('L') => input[1..].parse::<u16>().ok().map(Rotation::Left),
-            Some('R') => input[1..].parse::<u16>().ok().map(Rotation::Right),
-            _ => None,
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Lock {
     // invariant that position will always be between 0-99
@@ -70,22 +64,20 @@ impl Lock {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+fn unlock(input: &str) -> Result<Lock, ParseError> {
     let mut lock = Lock::new();
-    for line in input.lines() {
-        let rotation = Rotation::from(line).expect("Each line can be converted to a rotation");
+    for rotation in parse_lines::<Rotation>(input)? {
         lock.apply_rotation(&rotation);
     }
-    Some(lock.zero_resting_count)
+    Ok(lock)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut lock = Lock::new();
-    for line in input.lines() {
-        let rotation = Rotation::from(line).expect("Each line can be converted to a rotation");
-        lock.apply_rotation(&rotation);
-    }
-    Some(lock.zero_pass_count)
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    Ok(unlock(input)?.zero_resting_count)
+}
+
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    Ok(unlock(input)?.zero_pass_count)
 }
 
 #[cfg(test)]
@@ -95,12 +87,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
+        assert_eq!(result, Ok(3));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn test_invalid_rotation() {
+        let result = part_one("L68\nX30\n");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, column 1: expected `L` or `R`, found `X30`"
+        );
     }
 }
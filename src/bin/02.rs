@@ -1,65 +1,66 @@
-advent_of_code::solution!(2);
+use std::collections::BTreeSet;
 
-fn parse_range(input: &str) -> Option<std::ops::RangeInclusive<u64>> {
-    let (lhs, rhs) = input.split_once('-')?;
-    let lhs = lhs.parse::<u64>().ok()?;
-    let rhs = rhs.parse::<u64>().ok()?;
-    Some(lhs..=rhs)
-}
+use advent_of_code::{AocParse, IntervalSet};
 
-fn divisors_excluding_one(n: usize) -> impl Iterator<Item = usize> {
-    (2..=n).filter(move |&i| n.is_multiple_of(i))
-}
-
-fn get_invalid_ids<F>(range: &std::ops::RangeInclusive<u64>, pred: F) -> Vec<u64>
-where
-    F: Fn(&u64) -> bool,
-{
-    range.clone().filter(pred).collect()
-}
-
-fn is_n_repeating_slices(str: &str, slice_count: usize) -> bool {
-    debug_assert!(str.len().is_multiple_of(slice_count));
-    let slice_len = str.len() / slice_count;
-    let first_slice = &str[..slice_len];
-    (1..slice_count).all(|i| {
-        let start_idx = i * slice_len;
-        let end_idx = start_idx + slice_len;
-        &str[start_idx..end_idx] == first_slice
-    })
-}
-fn is_two_repeating_slices(id: &u64) -> bool {
-    let string = id.to_string();
-    string.len().is_multiple_of(2) && is_n_repeating_slices(&string, 2)
-}
+advent_of_code::solution!(2);
 
-fn has_any_repeating_slices(id: &u64) -> bool {
-    let string = id.to_string();
-    divisors_excluding_one(string.len()).any(|n| is_n_repeating_slices(&string, n))
+#[derive(AocParse)]
+#[aoc_parse("{start}-{end}")]
+struct Range {
+    start: u64,
+    end: u64,
 }
 
-fn parse_ranges(input: &str) -> Vec<std::ops::RangeInclusive<u64>> {
+fn parse_ranges(input: &str) -> IntervalSet<u64> {
     input
+        .trim()
         .split(',')
         .map(|range| {
-            parse_range(range).expect("can split input by ',' then parse into a valid range")
+            let range: Range = range
+                .parse()
+                .expect("can split input by ',' then parse into a valid range");
+            range.start..=range.end
         })
         .collect()
 }
+
+/// IDs up to `max` that consist of a block of digits repeated `repeats` times, e.g. 123123 for two repeats.
+fn repeated_ids(repeats: u32, max: u64) -> impl Iterator<Item = u64> {
+    (1..)
+        .map_while(move |block_len| {
+            let shift = 10u64.checked_pow(block_len)?;
+            // multiplying a block by e.g. 1001 repeats a block of three digits twice.
+            let multiplier =
+                (0..repeats).try_fold(0u64, |m, _| m.checked_mul(shift)?.checked_add(1))?;
+            let blocks = shift / 10..shift;
+            (blocks.start.checked_mul(multiplier)? <= max).then_some((blocks, multiplier))
+        })
+        .flat_map(move |(blocks, multiplier)| {
+            blocks.map_while(move |block| block.checked_mul(multiplier).filter(|id| *id <= max))
+        })
+}
+
+/// Sums the IDs within the ranges that consist of a block of digits repeated any of `repeats` times.
+fn invalid_id_sum(ranges: &IntervalSet<u64>, repeats: impl Iterator<Item = u32>) -> u64 {
+    let Some(max) = ranges.iter().next_back().map(|range| range.end - 1) else {
+        return 0;
+    };
+
+    // 1111 is both 1 repeated four times and 11 repeated twice.
+    let candidates: BTreeSet<u64> = repeats.flat_map(|n| repeated_ids(n, max)).collect();
+    candidates
+        .into_iter()
+        .filter(|id| ranges.contains(*id))
+        .sum()
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
-    let invalid_id_sum = parse_ranges(input)
-        .iter()
-        .flat_map(|range| get_invalid_ids(range, is_two_repeating_slices))
-        .sum();
-    Some(invalid_id_sum)
+    Some(invalid_id_sum(&parse_ranges(input), 2..=2))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let invalid_id_sum = parse_ranges(input)
-        .iter()
-        .flat_map(|range| get_invalid_ids(range, has_any_repeating_slices))
-        .sum();
-    Some(invalid_id_sum)
+    // u64 has at most 20 digits.
+    Some(invalid_id_sum(&parse_ranges(input), 2..=20))
 }
 
 #[cfg(test)]
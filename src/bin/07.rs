@@ -1,32 +1,19 @@
-use itertools::Itertools;
-use std::cmp::PartialEq;
+use advent_of_code::{FromChar, Grid, ParseError};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, solve = solve);
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, FromChar)]
 enum Space {
+    #[char = 'S']
     Source,
+    #[char = '.']
     Empty,
+    #[char = '^']
     Splitter,
 }
 
-impl Space {
-    fn from(c: char) -> Option<Self> {
-        match c {
-            'S' => Some(Space::Source),
-            '.' => Some(Space::Empty),
-            '^' => Some(Space::Splitter),
-            _ => None,
-        }
-    }
-}
-
-fn read_line(input: &str) -> Vec<Space> {
-    input.trim().chars().filter_map(Space::from).collect()
-}
-
-fn read_map(input: &str) -> Vec<Vec<Space>> {
-    input.lines().map(read_line).collect()
+fn read_map(input: &str) -> Result<Grid<Space>, ParseError> {
+    Grid::parse(input, Space::try_from)
 }
 
 fn apply_new_line(current: &[u64], line: &[Space], next: &mut [u64]) -> u64 {
@@ -53,37 +40,29 @@ fn apply_new_line(current: &[u64], line: &[Space], next: &mut [u64]) -> u64 {
     split_count
 }
 
-fn track_beam_progress(map: &[Vec<Space>]) -> (u64, u64) {
-    let width = map[0].len();
-    let mut current_line = vec![0; width];
-    let mut next = vec![0; width];
-    debug_assert_eq!(map[0].iter().filter(|s| **s == Space::Source).count(), 1);
-    let source_idx = map[0]
-        .iter()
-        .find_position(|s| **s == Space::Source)
-        .unwrap()
-        .0;
+fn track_beam_progress(map: &Grid<Space>) -> Result<(u64, u64), ParseError> {
+    let mut current_line = vec![0; map.width()];
+    let mut next = vec![0; map.width()];
+    let (source_idx, _) = map
+        .find(|s| *s == Space::Source)
+        .filter(|(_, y)| *y == 0)
+        .ok_or_else(|| ParseError::new("expected a source `S` in the first row").at_line(1))?;
     current_line[source_idx] = 1;
     let mut split_beam_count = 0;
-    for line in map.iter().skip(1) {
+    for line in map.rows().skip(1) {
         let splits = apply_new_line(&current_line, line, &mut next);
         split_beam_count += splits;
         // Reuse buffer
         std::mem::swap(&mut current_line, &mut next);
     }
-    (split_beam_count, current_line.iter().sum::<u64>())
-}
-
-pub fn part_one(input: &str) -> Option<u64> {
-    let map = read_map(input);
-    debug_assert!(map.iter().map(|l| l.len()).all_equal());
-    Some(track_beam_progress(&map).0)
+    Ok((split_beam_count, current_line.iter().sum::<u64>()))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let map = read_map(input);
-    debug_assert!(map.iter().map(|l| l.len()).all_equal());
-    Some(track_beam_progress(&map).1)
+pub fn solve(input: &str) -> (Result<u64, ParseError>, Result<u64, ParseError>) {
+    match read_map(input).and_then(|map| track_beam_progress(&map)) {
+        Ok((split_beam_count, timeline_count)) => (Ok(split_beam_count), Ok(timeline_count)),
+        Err(e) => (Err(e.clone()), Err(e)),
+    }
 }
 
 #[cfg(test)]
@@ -92,13 +71,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+        let (result, _) = solve(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
+        let (_, result) = solve(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(40));
     }
 }
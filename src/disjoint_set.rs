@@ -0,0 +1,150 @@
+//! Union-find over the elements `0..n`, e.g. to track connected components.
+
+/// Partition of the elements `0..n` into disjoint components. Starts with every element in a
+/// component of its own; [`DisjointSet::union`] merges components.
+///
+/// Uses path compression and union by size, so all operations run in nearly constant time.
+///
+/// ```
+/// # use advent_of_code::DisjointSet;
+/// let mut components = DisjointSet::new(5);
+/// assert!(components.union(0, 1));
+/// assert!(components.union(1, 2));
+/// assert!(!components.union(0, 2));
+///
+/// assert!(components.connected(0, 2));
+/// assert_eq!(components.component_count(), 3);
+/// assert_eq!(components.component_size(1), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    /// Size of the component of each root. Not maintained for other elements.
+    sizes: Vec<usize>,
+    component_count: usize,
+}
+
+impl DisjointSet {
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            component_count: len,
+        }
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The representative of the component of `x`. Panics if `x` is out of bounds.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // point every element on the path directly to the root.
+        let mut x = x;
+        while self.parents[x] != root {
+            let parent = self.parents[x];
+            self.parents[x] = root;
+            x = parent;
+        }
+
+        root
+    }
+
+    /// Merge the components of `a` and `b`. Returns `false` if they were already connected.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (large, small) = if self.sizes[a] >= self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.component_count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// The number of elements in the component of `x`.
+    pub fn component_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+
+    /// The sizes of all components, in no particular order.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(|(x, parent)| *x == **parent)
+            .map(|(root, _)| self.sizes[root])
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DisjointSet;
+
+    #[test]
+    fn merges_components() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.len(), 6);
+        assert_eq!(set.component_count(), 6);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(3, 4));
+        assert!(!set.union(4, 2));
+
+        assert!(set.connected(2, 4));
+        assert!(!set.connected(1, 2));
+        assert_eq!(set.component_count(), 3);
+
+        let mut sizes = set.component_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 2, 3]);
+
+        assert!(set.union(1, 4));
+        assert_eq!(set.component_size(0), 5);
+        assert_eq!(set.component_size(5), 1);
+        assert_eq!(set.component_count(), 2);
+    }
+
+    #[test]
+    fn compresses_paths() {
+        let mut set = DisjointSet::new(4);
+        set.union(0, 1);
+        set.union(2, 3);
+        set.union(0, 2);
+
+        let root = set.find(3);
+        assert!((0..4).all(|x| set.find(x) == root));
+        assert!((0..4).all(|x| set.parents[x] == root));
+        assert!(DisjointSet::new(0).is_empty());
+    }
+}
@@ -0,0 +1,441 @@
+//! A two-dimensional grid of cells, as used by many puzzle inputs.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// Position of a cell as `(x, y)`: `x` is the column, `y` the row, starting at the top left.
+pub type Position = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours: up, right, down, left.
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours, clockwise starting at the top left.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid, stored row by row in a single `Vec`.
+///
+/// ```
+/// # use advent_of_code::Grid;
+/// let grid: Grid<char> = "#..\n.#.".parse().unwrap();
+/// assert_eq!((grid.width(), grid.height()), (3, 2));
+/// assert_eq!(grid[(1, 1)], '#');
+/// assert_eq!(grid.neighbours4((0, 0)).count(), 2);
+/// assert_eq!(grid.to_string(), "#..\n.#.");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` x `height` cells set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid from its rows. Returns `None` if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parse a grid with one line per row, mapping each character to a cell.
+    /// Errors point at the offending character, and at the first line of a different length.
+    ///
+    /// Only line endings (`\n` or `\r\n`) are stripped, as spaces can be cells. Trim the input beforehand
+    /// if it may have trailing blank lines or whitespace.
+    ///
+    /// ```
+    /// # use advent_of_code::Grid;
+    /// let parse = |c| match c {
+    ///     '#' => Ok(true),
+    ///     '.' => Ok(false),
+    ///     c => Err(format!("unexpected `{c}`")),
+    /// };
+    /// assert_eq!(Grid::parse("#.\n.#", parse).unwrap()[(0, 0)], true);
+    /// assert_eq!(
+    ///     Grid::parse("#.\n.x", parse).unwrap_err().to_string(),
+    ///     "line 2, column 2: unexpected `x`"
+    /// );
+    /// ```
+    pub fn parse<E: Into<ParseError>>(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            for (x, c) in line.chars().enumerate() {
+                let cell = f(c).map_err(|e| e.into().at_line(y + 1).at_column(x + 1))?;
+                cells.push(cell);
+            }
+
+            let line_width = line.chars().count();
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(ParseError::new(format!(
+                        "expected a row of {width} cells, found {line_width}"
+                    ))
+                    .at_line(y + 1));
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// The cell at `position` moved by `(dx, dy)`, if that is still inside the grid.
+    pub fn offset(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// The up to four orthogonal neighbours of a cell: up, right, down, left.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_4
+            .iter()
+            .filter_map(move |offset| self.offset(position, *offset))
+    }
+
+    /// The up to eight neighbours of a cell, including diagonals.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_8
+            .iter()
+            .filter_map(move |offset| self.offset(position, *offset))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    /// Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Panics if `y` is out of bounds.
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks_exact` panics on a chunk size of zero, an empty grid has no cells anyway.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Position of the first cell, row by row, that matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirror the grid along its main diagonal: rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |(x, y)| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |(x, y)| (y, self.height - 1 - x))
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |(x, y)| (self.width - 1 - y, x))
+    }
+
+    /// A grid of the given size, each cell copied from the position `source` maps it to.
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(Position) -> Position) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|position| self[source(position)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.1 * self.width + position.0)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        match self.index_of(position) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "position {position:?} out of bounds of a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        match self.index_of(position) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "position {position:?} out of bounds of a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, Ok::<_, ParseError>)
+    }
+}
+
+/// Writes one line per row, without separators between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                f.write_str("\n")?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::FromChar;
+
+    #[derive(Clone, Copy, Debug, PartialEq, FromChar)]
+    enum Cell {
+        #[char = '#']
+        Wall,
+        #[char = '.']
+        Open,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, FromChar)]
+    enum Bracket {
+        #[char = '{']
+        Open,
+        #[char = '}']
+        Close,
+    }
+
+    fn grid(input: &str) -> Grid<char> {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_text() {
+        let g = grid("abc\ndef\n");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        assert_eq!(g.column(2).collect::<String>(), "cf");
+        assert_eq!(g.to_string(), "abc\ndef");
+
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>().unwrap_err().to_string(),
+            "line 2: expected a row of 2 cells, found 1"
+        );
+        assert_eq!(grid("a \r\n b\r\n").row(0), ['a', ' ']);
+        assert_eq!(grid(" b\n c").column(0).collect::<String>(), "  ");
+        assert_eq!(
+            "ab\ncd\n\n".parse::<Grid<char>>().unwrap_err().to_string(),
+            "line 3: expected a row of 2 cells, found 0"
+        );
+        assert_eq!(grid("").positions().count(), 0);
+        assert_eq!(grid("").rows().count(), 0);
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut g = grid("ab\ncd");
+        assert_eq!(g.get((1, 1)), Some(&'d'));
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(g.offset((0, 0), (-1, 0)), None);
+        assert_eq!(g.offset((0, 0), (1, 1)), Some((1, 1)));
+
+        *g.get_mut((0, 1)).unwrap() = 'x';
+        g[(1, 0)] = 'y';
+        assert_eq!(g.to_string(), "ay\nxd");
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let g = grid("abc\ndef\nghi");
+        let at = |positions: Vec<_>| positions.into_iter().map(|p| g[p]).collect::<String>();
+
+        assert_eq!(at(g.neighbours4((1, 1)).collect()), "bfhd");
+        assert_eq!(at(g.neighbours4((0, 0)).collect()), "bd");
+        assert_eq!(at(g.neighbours8((1, 1)).collect()), "abcfihgd");
+        assert_eq!(at(g.neighbours8((2, 2)).collect()), "efh");
+    }
+
+    #[test]
+    fn finds_and_maps_cells() {
+        let g = grid("ab\ncb");
+        assert_eq!(g.find(|c| *c == 'b'), Some((1, 0)));
+        assert_eq!(g.find(|c| *c == 'z'), None);
+        assert_eq!(g.map(|c| c.to_ascii_uppercase()).to_string(), "AB\nCB");
+        assert_eq!(g.iter().filter(|(_, c)| **c == 'b').count(), 2);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(g.rotate_clockwise().rotate_counterclockwise(), g);
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).map(|g| g.transpose().to_string()),
+            Some("13\n24".into())
+        );
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+
+    #[test]
+    fn round_trips_derived_cells() {
+        let g = Grid::parse("#.\n.#", Cell::try_from).unwrap();
+        assert_eq!(g[(0, 0)], Cell::Wall);
+        assert_eq!(g[(1, 0)], Cell::Open);
+        assert_eq!(g.to_string(), "#.\n.#");
+
+        assert_eq!(
+            Grid::parse("#.\n.x", Cell::try_from)
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 2: expected one of `#`, `.` for `Cell`, found `x`"
+        );
+    }
+
+    #[test]
+    fn derives_brace_cells() {
+        let g = Grid::parse("{}", Bracket::try_from).unwrap();
+        assert_eq!(g.row(0), [Bracket::Open, Bracket::Close]);
+        assert_eq!(g.to_string(), "{}");
+
+        assert_eq!(
+            Bracket::try_from('(').unwrap_err().to_string(),
+            "expected one of `{`, `}` for `Bracket`, found `(`"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Sets of integers stored as sorted, disjoint ranges.
+
+use std::fmt::Debug;
+use std::ops::{Add, Bound, Range, RangeBounds, RangeInclusive, Sub};
+
+/// Integer types that can be stored in an [`IntervalSet`].
+pub trait Endpoint: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    /// The number of values in `start..end`, which does not fit every type itself, e.g. `i32::MIN..0`.
+    fn distance(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {
+        $(
+            impl Endpoint for $t {
+                const ONE: Self = 1;
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn distance(start: Self, end: Self) -> u128 {
+                    end.abs_diff(start) as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_endpoint!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A set of integers, stored as sorted half-open ranges that neither overlap nor touch.
+///
+/// Ranges can be given in any form: `a..b`, `a..=b`, `a..` or `..b`. As ranges are stored half-open,
+/// `T::MAX` itself cannot be part of the set: `a..` ends before it.
+///
+/// # Panics
+///
+/// Methods that take a range panic if the range includes `T::MAX` explicitly, e.g. `a..=T::MAX`.
+///
+/// ```
+/// # use advent_of_code::IntervalSet;
+/// let mut set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+/// assert_eq!(set.iter().collect::<Vec<_>>(), [3..6, 10..21]);
+/// assert!(set.contains(17));
+/// assert!(!set.contains(7));
+/// assert_eq!(set.len(), 14);
+///
+/// set.remove(4..12);
+/// assert_eq!(set.iter_inclusive().collect::<Vec<_>>(), [3..=3, 12..=20]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+
+    /// Add a range, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: impl RangeBounds<T>) {
+        let Range { start, end } = half_open(&range);
+        if start >= end {
+            return;
+        }
+
+        // ranges that overlap or touch `start..end`.
+        let i = self.ranges.partition_point(|r| r.end < start);
+        let j = self.ranges.partition_point(|r| r.start <= end);
+
+        let merged = if i < j {
+            start.min(self.ranges[i].start)..end.max(self.ranges[j - 1].end)
+        } else {
+            start..end
+        };
+
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    /// Remove a range, splitting the ranges it cuts into.
+    pub fn remove(&mut self, range: impl RangeBounds<T>) {
+        let Range { start, end } = half_open(&range);
+        if start >= end {
+            return;
+        }
+
+        // ranges that overlap `start..end`.
+        let i = self.ranges.partition_point(|r| r.end <= start);
+        let j = self.ranges.partition_point(|r| r.start < end);
+        if i >= j {
+            return;
+        }
+
+        let left = self.ranges[i].start..start;
+        let right = end..self.ranges[j - 1].end;
+        let rest = [left, right].into_iter().filter(|r| r.start < r.end);
+
+        self.ranges.splice(i..j, rest);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// Whether the whole range is part of the set. Empty ranges are always contained.
+    pub fn contains_range(&self, range: impl RangeBounds<T>) -> bool {
+        let Range { start, end } = half_open(&range);
+        if start >= end {
+            return true;
+        }
+
+        let i = self.ranges.partition_point(|r| r.end <= start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.start <= start && end <= r.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for range in &other.ranges {
+            set.insert(range.clone());
+        }
+        set
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if overlap.start < overlap.end {
+                ranges.push(overlap);
+            }
+
+            // the range that ends first cannot overlap any further range of the other set.
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for range in &other.ranges {
+            set.remove(range.clone());
+        }
+        set
+    }
+
+    /// Split the set into the values below `at` and the values from `at` on.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = self.clone();
+        below.remove(at..);
+        let mut above = self.clone();
+        above.remove(..at);
+        (below, above)
+    }
+
+    /// The number of values in the set. Counted as `u128`, as it does not fit `T` for sets like `..0_i32`.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|r| T::distance(r.start, r.end))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The ranges of the set in ascending order, half-open.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    /// The ranges of the set in ascending order, inclusive.
+    pub fn iter_inclusive(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|r| r.start..=r.end - T::ONE)
+    }
+}
+
+impl<T: Endpoint, R: RangeBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Endpoint, R: RangeBounds<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+/// Converts range bounds into a half-open range. Panics if an inclusive end is `T::MAX`.
+fn half_open<T: Endpoint>(range: &impl RangeBounds<T>) -> Range<T> {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) if *start == T::MAX => return T::MAX..T::MAX,
+        Bound::Excluded(start) => *start + T::ONE,
+        Bound::Unbounded => T::MIN,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(end) => {
+            assert!(*end < T::MAX, "interval sets cannot contain {:?}", T::MAX);
+            *end + T::ONE
+        }
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => T::MAX,
+    };
+
+    start..end
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::IntervalSet;
+
+    fn set(ranges: &[std::ops::Range<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<std::ops::Range<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn merges_inserted_ranges() {
+        let mut s = set(&[0..2, 5..7]);
+        s.insert(2..3);
+        assert_eq!(ranges(&s), [0..3, 5..7]);
+
+        s.insert(-5..=-3);
+        s.insert(4..4);
+        assert_eq!(ranges(&s), [-5..-2, 0..3, 5..7]);
+
+        s.insert(-2..6);
+        assert_eq!(ranges(&s), [-5..7]);
+        assert_eq!(s.len(), 12);
+    }
+
+    #[test]
+    fn removes_ranges() {
+        let mut s = set(&[0..10, 20..30]);
+        s.remove(5..25);
+        assert_eq!(ranges(&s), [0..5, 25..30]);
+
+        s.remove(2..=2);
+        assert_eq!(ranges(&s), [0..2, 3..5, 25..30]);
+
+        s.remove(10..20);
+        s.remove(..=3);
+        assert_eq!(ranges(&s), [4..5, 25..30]);
+
+        s.remove(..);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn checks_containment() {
+        let s = set(&[0..3, 5..7]);
+        assert!(s.contains(0));
+        assert!(s.contains(2));
+        assert!(!s.contains(3));
+        assert!(!s.contains(-1));
+        assert!(s.contains(6));
+        assert!(!s.contains(7));
+
+        assert!(s.contains_range(5..=6));
+        assert!(!s.contains_range(2..6));
+        assert!(s.contains_range(9..9));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[0..5, 10..15]);
+        let b = set(&[3..12, 14..20]);
+
+        assert_eq!(ranges(&a.union(&b)), [0..20]);
+        assert_eq!(ranges(&a.intersection(&b)), [3..5, 10..12, 14..15]);
+        assert_eq!(ranges(&a.difference(&b)), [0..3, 12..14]);
+        assert_eq!(ranges(&b.difference(&a)), [5..10, 15..20]);
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn splits_sets() {
+        let (below, above) = set(&[0..5, 10..15]).split_at(3);
+        assert_eq!(ranges(&below), [0..3]);
+        assert_eq!(ranges(&above), [3..5, 10..15]);
+
+        let (below, above) = set(&[0..5]).split_at(5);
+        assert_eq!(ranges(&below), [0..5]);
+        assert!(above.is_empty());
+    }
+
+    #[test]
+    fn counts_values_beyond_the_endpoint_type() {
+        let mut s = IntervalSet::<i32>::new();
+        s.insert(..0);
+        assert_eq!(s.len(), 1 << 31);
+
+        s.insert(0..);
+        assert_eq!(s.len(), u128::from(u32::MAX));
+        assert!(s.contains(i32::MAX - 1));
+        assert!(!s.contains(i32::MAX));
+
+        let s: IntervalSet<u128> = [0..u128::MAX].into_iter().collect();
+        assert_eq!(s.len(), u128::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval sets cannot contain 255")]
+    fn panics_for_inclusive_max() {
+        IntervalSet::<u8>::new().insert(200..=u8::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval sets cannot contain 127")]
+    fn panics_for_inclusive_max_without_start() {
+        IntervalSet::<i8>::new().remove(..=i8::MAX);
+    }
+
+    #[test]
+    fn iterates_inclusive_ranges() {
+        let s: IntervalSet<u8> = [1..=3, 7..=7].into_iter().collect();
+        assert_eq!(s.iter_inclusive().collect::<Vec<_>>(), [1..=3, 7..=7]);
+        assert_eq!(s.iter_inclusive().next_back(), Some(7..=7));
+    }
+}
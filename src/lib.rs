@@ -1,3 +1,16 @@
+// lets the derive macros refer to `::advent_of_code` from within this crate as well.
+extern crate self as advent_of_code;
+
+pub mod disjoint_set;
+pub mod grid;
+pub mod interval_set;
+pub mod parse;
 pub mod template;
 
+pub use advent_of_code_derive::{AocParse, FromChar};
+pub use disjoint_set::DisjointSet;
+pub use grid::Grid;
+pub use interval_set::IntervalSet;
+pub use parse::{parse_lines, ParseError};
+
 // Use this file to add helper functions and additional modules.
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, refresh_tests, run_day, scaffold, session, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -6,9 +8,21 @@ use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+/// Solutions compiled into this binary so that `all` and `time` can run them in-process.
+/// NOTE: the registry is left empty in tests and DHAT builds, as every solution defines its own tests and allocator.
+#[cfg(not(any(test, feature = "dhat-heap")))]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
+#[cfg(any(test, feature = "dhat-heap"))]
+mod registry {
+    pub const DAYS: &[advent_of_code::template::runner::RegisteredDay] = &[];
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{commands::session, day_count, Day, Year};
+    use std::{env, process, time::Duration};
 
     pub enum AppArguments {
         Download {
@@ -17,10 +31,20 @@ mod args {
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            overwrite: bool,
+        },
+        RefreshTests {
+            day: Day,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            year: Option<Year>,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
             day: Day,
@@ -30,11 +54,27 @@ mod args {
         },
         All {
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+        },
+        Verify {
+            day: Option<Day>,
+            record: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+        },
+        Session {
+            action: session::Action,
+        },
+        RunDay {
+            day: Day,
         },
         #[cfg(feature = "today")]
         Today,
@@ -43,33 +83,95 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // NOTE: the year is set before parsing days, as the number of days depends on it.
+        // child processes, e.g. `cargo run` for a solution, inherit it as well.
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+        if let Some(year) = year {
+            env::set_var("AOC_YEAR", year.to_string());
+        }
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                timeout: parse_timeout(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                // NOTE: timed runs stay sequential unless asked otherwise, concurrent days skew timings.
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let timeout = parse_timeout(&mut args)?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: args.opt_free_from_fn(parse_day)?,
                     store,
+                    jobs,
+                    timeout,
+                }
+            }
+            Some("verify") => {
+                let record = args.contains("--record");
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let timeout = parse_timeout(&mut args)?;
+
+                AppArguments::Verify {
+                    day: args.opt_free_from_fn(parse_day)?,
+                    record,
+                    jobs,
+                    timeout,
+                }
+            }
+            Some("session") => {
+                let action = match args.subcommand()?.as_deref() {
+                    Some("set") => session::Action::Set(args.opt_free_from_str()?),
+                    Some("show") | None => session::Action::Show,
+                    Some("check") => session::Action::Check,
+                    Some(x) => {
+                        eprintln!(
+                            "Unknown session command: {x}. Expected one of: set, show, check."
+                        );
+                        process::exit(1);
+                    }
+                };
+
+                AppArguments::Session { action }
+            }
+            Some("run-day") => {
+                // NOTE: `--time` and `--results` are read by the runner directly.
+                let _ = args.contains("--time");
+                let _: Option<String> = args.opt_value_from_str("--results")?;
+
+                AppArguments::RunDay {
+                    day: args.free_from_fn(parse_day)?,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                day: args.free_from_fn(parse_day)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                day: args.free_from_fn(parse_day)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                overwrite: args.contains("--overwrite"),
+                day: args.free_from_fn(parse_day)?,
+            },
+            Some("refresh-tests") => AppArguments::RefreshTests {
+                day: args.free_from_fn(parse_day)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                day: args.free_from_fn(parse_day)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                year,
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                day: args.free_from_fn(parse_day)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
@@ -93,6 +195,26 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses a day of the current event. Unlike `Day::from_str`, this rejects days after the
+    /// last puzzle of a short event, e.g. the 13th in 2025.
+    fn parse_day(s: &str) -> Result<Day, String> {
+        let error = || format!("expecting a day number between 1 and {}", day_count());
+        let day: Day = s.parse().map_err(|_| error())?;
+
+        match Year::current() {
+            Some(year) if !year.has_day(day) => Err(error()),
+            _ => Ok(day),
+        }
+    }
+
+    /// Parses `--timeout <seconds>`.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+        Ok(timeout.map(Duration::from_secs_f64))
+    }
 }
 
 fn main() {
@@ -102,16 +224,45 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                jobs,
+                timeout,
+            } => all::handle(release, jobs, timeout, registry::DAYS),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                jobs,
+                timeout,
+            } => time::handle(day, all, store, jobs, timeout, registry::DAYS),
+            AppArguments::Verify {
+                day,
+                record,
+                jobs,
+                timeout,
+            } => verify::handle(day, record, jobs, timeout, registry::DAYS),
+            AppArguments::Session { action } => session::handle(action),
+            AppArguments::RunDay { day } => run_day::handle(day, registry::DAYS),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Examples { day, overwrite } => examples::handle(day, overwrite),
+            AppArguments::RefreshTests { day } => refresh_tests::handle(day),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                year,
+                template,
+                answer_type,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(
+                    day,
+                    overwrite,
+                    year,
+                    template.as_deref(),
+                    answer_type.as_deref(),
+                );
                 if download {
                     download::handle(day);
                 }
@@ -126,14 +277,14 @@ fn main() {
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
+                        scaffold::handle(day, false, None, None, None);
                         download::handle(day);
                         read::handle(day)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during the days of this year's \
+                            event in december. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,390 @@
+//! Helpers to parse puzzle inputs without panicking on unexpected input.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::FromStr;
+
+/// An error in the puzzle input. Line and column are 1-based and point at the offending input, if known.
+///
+/// ```
+/// # use advent_of_code::ParseError;
+/// let error = ParseError::new("expected `L` or `R`").at_line(3).at_column(1);
+/// assert_eq!(error.to_string(), "line 3, column 1: expected `L` or `R`");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ParseError {
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Locate an error by its byte offset into `input`.
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &input[..offset.min(input.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError::new(message)
+            .at_line(before.matches('\n').count() + 1)
+            .at_column(before[line_start..].chars().count() + 1)
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Move the error down by `lines`, e.g. when it occurred in a section further down the input.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line = self.line.map(|line| line + lines);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            (Some(line), None) => write!(f, "line {line}: ")?,
+            (None, Some(column)) => write!(f, "column {column}: ")?,
+            (None, None) => {}
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<String> for ParseError {
+    fn from(message: String) -> Self {
+        ParseError::new(message)
+    }
+}
+
+impl From<&str> for ParseError {
+    fn from(message: &str) -> Self {
+        ParseError::new(message)
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(e: ParseIntError) -> Self {
+        ParseError::new(format!("invalid number: {e}"))
+    }
+}
+
+impl From<ParseFloatError> for ParseError {
+    fn from(e: ParseFloatError) -> Self {
+        ParseError::new(format!("invalid number: {e}"))
+    }
+}
+
+/// Parse each line of the input, stopping at the first line that fails. The error points at that line.
+///
+/// ```
+/// # use advent_of_code::parse_lines;
+/// assert_eq!(parse_lines::<u32>("1\n2\n"), Ok(vec![1, 2]));
+/// assert_eq!(
+///     parse_lines::<u32>("1\nx").unwrap_err().to_string(),
+///     "line 2: invalid number: invalid digit found in string"
+/// );
+/// ```
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Into<ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse().map_err(|e: T::Err| {
+                let e: ParseError = e.into();
+                e.at_line(i + 1)
+            })
+        })
+        .collect()
+}
+
+/// A piece of a line pattern of `#[derive(AocParse)]`: text that has to match as is, or the value of a field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternPart {
+    Literal(&'static str),
+    Field,
+}
+
+/// Why a line did not match a pattern: what was expected at a byte offset of the line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternMismatch {
+    pub offset: usize,
+    pub expected: String,
+}
+
+impl PatternMismatch {
+    /// Describe why none of the patterns matched, by the mismatches that got furthest into the line.
+    pub fn into_error(input: &str, mismatches: Vec<PatternMismatch>) -> ParseError {
+        let offset = mismatches.iter().map(|m| m.offset).max().unwrap_or(0);
+
+        let mut expected: Vec<String> = vec![];
+        for mismatch in mismatches.into_iter().filter(|m| m.offset == offset) {
+            if !expected.contains(&mismatch.expected) {
+                expected.push(mismatch.expected);
+            }
+        }
+
+        let found = match &input[offset..] {
+            "" => "end of line".to_string(),
+            rest => format!("`{rest}`"),
+        };
+
+        ParseError::new(format!("expected {}, found {found}", expected.join(" or ")))
+            .at_column(column(input, offset))
+    }
+}
+
+/// Split a line along a pattern. Returns the byte offset and text of each field.
+/// A field extends up to the first occurrence of the text that follows it, the last field up to the end of the line.
+pub fn match_pattern<'a>(
+    input: &'a str,
+    pattern: &[PatternPart],
+) -> Result<Vec<(usize, &'a str)>, PatternMismatch> {
+    let mut values = vec![];
+    let mut offset = 0;
+
+    for (i, part) in pattern.iter().enumerate() {
+        let rest = &input[offset..];
+
+        match part {
+            PatternPart::Literal(literal) => {
+                if !rest.starts_with(literal) {
+                    return Err(PatternMismatch {
+                        offset,
+                        expected: format!("`{literal}`"),
+                    });
+                }
+                offset += literal.len();
+            }
+            PatternPart::Field => {
+                let len = match pattern.get(i + 1) {
+                    // without the text that ends it, the field cannot be told apart, so point at its start.
+                    Some(PatternPart::Literal(next)) => {
+                        rest.find(next).ok_or_else(|| PatternMismatch {
+                            offset,
+                            expected: format!("a value followed by `{next}`"),
+                        })?
+                    }
+                    _ => rest.len(),
+                };
+                values.push((offset, &rest[..len]));
+                offset += len;
+            }
+        }
+    }
+
+    if offset < input.len() {
+        return Err(PatternMismatch {
+            offset,
+            expected: "end of line".into(),
+        });
+    }
+
+    Ok(values)
+}
+
+/// Parse the text of a field matched by [`match_pattern`]. Errors point at the start of the field.
+pub fn parse_field<T>(
+    input: &str,
+    (offset, value): (usize, &str),
+    name: &str,
+) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse().map_err(|e| {
+        ParseError::new(format!("invalid `{name}` `{value}`: {e}")).at_column(column(input, offset))
+    })
+}
+
+/// The 1-based column of a byte offset into a line.
+fn column(input: &str, offset: usize) -> usize {
+    input[..offset].chars().count() + 1
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::AocParse;
+
+    use super::{
+        match_pattern, parse_field, parse_lines, ParseError, PatternMismatch, PatternPart,
+    };
+
+    #[test]
+    fn locates_offsets() {
+        let input = "abc\ndéf\nghi";
+        let error = ParseError::at_offset(input, input.find('f').unwrap(), "bad");
+
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.column, Some(3));
+        assert_eq!(
+            ParseError::at_offset(input, 0, "bad").to_string(),
+            "line 1, column 1: bad"
+        );
+        assert_eq!(ParseError::at_offset(input, 99, "bad").line, Some(3));
+    }
+
+    #[test]
+    fn keeps_columns_of_line_errors() {
+        struct Digit;
+
+        impl std::str::FromStr for Digit {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.find(|c: char| !c.is_ascii_digit()) {
+                    Some(i) => Err(ParseError::new("expected a digit").at_column(i + 1)),
+                    None => Ok(Digit),
+                }
+            }
+        }
+
+        let error = parse_lines::<Digit>("12\n3a").err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 2: expected a digit");
+        assert_eq!(error.offset_lines(10).line, Some(12));
+    }
+
+    #[test]
+    fn matches_patterns() {
+        use PatternPart::{Field, Literal};
+        let range = [Field, Literal("-"), Field];
+
+        assert_eq!(match_pattern("3-15", &range), Ok(vec![(0, "3"), (2, "15")]));
+        assert_eq!(
+            match_pattern(
+                "move 1 from 2",
+                &[Literal("move "), Field, Literal(" from "), Field]
+            ),
+            Ok(vec![(5, "1"), (12, "2")])
+        );
+
+        let mismatch = match_pattern("315", &range).unwrap_err();
+        assert_eq!(
+            PatternMismatch::into_error("315", vec![mismatch]).to_string(),
+            "column 1: expected a value followed by `-`, found `315`"
+        );
+
+        let mismatch =
+            match_pattern("3-15", &[Field, Literal("-"), Field, Literal("!")]).unwrap_err();
+        assert_eq!(mismatch.offset, 2);
+        assert_eq!(
+            PatternMismatch::into_error("3-15", vec![mismatch]).to_string(),
+            "column 3: expected a value followed by `!`, found `15`"
+        );
+        assert_eq!(
+            match_pattern("ab", &[Literal("a")]).unwrap_err().expected,
+            "end of line"
+        );
+    }
+
+    #[test]
+    fn merges_mismatches_of_alternatives() {
+        use PatternPart::{Field, Literal};
+
+        let mismatches = [[Literal("L"), Field], [Literal("R"), Field]]
+            .iter()
+            .map(|pattern| match_pattern("X30", pattern).unwrap_err())
+            .collect();
+
+        assert_eq!(
+            PatternMismatch::into_error("X30", mismatches).to_string(),
+            "column 1: expected `L` or `R`, found `X30`"
+        );
+    }
+
+    #[test]
+    fn parses_fields() {
+        assert_eq!(parse_field::<u8>("x=12", (2, "12"), "x"), Ok(12));
+        assert_eq!(
+            parse_field::<u8>("x=a", (2, "a"), "x")
+                .unwrap_err()
+                .to_string(),
+            "column 3: invalid `x` `a`: invalid digit found in string"
+        );
+    }
+
+    #[derive(Debug, PartialEq, AocParse)]
+    #[aoc_parse("{x},{y} ({{{label}}})")]
+    struct Point {
+        x: i32,
+        y: i32,
+        label: String,
+    }
+
+    #[derive(Debug, PartialEq, AocParse)]
+    enum Instruction {
+        #[aoc_parse("L{0}")]
+        Left(u16),
+        #[aoc_parse("R{0}")]
+        Right(u16),
+        #[aoc_parse("wait")]
+        Wait,
+    }
+
+    #[test]
+    fn derives_from_str_for_structs() {
+        assert_eq!(
+            "3,-4 ({a})".parse(),
+            Ok(Point {
+                x: 3,
+                y: -4,
+                label: "a".into()
+            })
+        );
+        assert_eq!(
+            "3,x ({a})".parse::<Point>().unwrap_err().to_string(),
+            "column 3: invalid `y` `x`: invalid digit found in string"
+        );
+        assert_eq!(
+            parse_lines::<Point>("1,2 ({a})\n1;2 ({b})")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: expected a value followed by `,`, found `1;2 ({b})`"
+        );
+    }
+
+    #[test]
+    fn derives_from_str_for_enums() {
+        assert_eq!(
+            parse_lines("L68\nR5\nwait"),
+            Ok(vec![
+                Instruction::Left(68),
+                Instruction::Right(5),
+                Instruction::Wait
+            ])
+        );
+        assert_eq!(
+            "X30".parse::<Instruction>().unwrap_err().to_string(),
+            "column 1: expected `L` or `R` or `wait`, found `X30`"
+        );
+        assert_eq!(
+            "Lx".parse::<Instruction>().unwrap_err().to_string(),
+            "column 2: invalid `0` `x`: invalid digit found in string"
+        );
+    }
+}
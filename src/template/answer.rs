@@ -0,0 +1,167 @@
+//! The result of a solution part, as understood by the runner.
+
+use std::fmt::{self, Display};
+
+/// An answer of a solution part.
+///
+/// Parts can return anything that converts into an [`Answer`]: integers, strings, [`Option`]s of these
+/// (`None` is [`Answer::Unsolved`]) and [`Result`]s (`Err` is [`Answer::Error`]).
+///
+/// ```
+/// # use advent_of_code::template::Answer;
+/// assert_eq!(Answer::from(Some(42_u64)), Answer::Integer(42));
+/// assert_eq!(Answer::from(None::<u64>), Answer::Unsolved);
+/// assert_eq!(Answer::from("#.\n.#"), Answer::Grid("#.\n.#".into()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// A multi-line rendering, e.g. letters drawn with `#` and `.`, that has to be read before it can be submitted.
+    Grid(String),
+    Unsolved,
+    Error(String),
+}
+
+impl Answer {
+    /// Whether the answer can be submitted as is.
+    pub fn is_submittable(&self) -> bool {
+        matches!(self, Answer::Integer(_) | Answer::Text(_))
+    }
+
+    /// Whether the part produced an answer, including grids that still have to be read.
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Answer::Integer(_) | Answer::Text(_) | Answer::Grid(_))
+    }
+
+    /// The answer as it is printed, submitted and stored. `None` if the part did not produce an answer.
+    pub fn value(&self) -> Option<String> {
+        match self {
+            Answer::Integer(x) => Some(x.to_string()),
+            Answer::Text(x) | Answer::Grid(x) => Some(x.clone()),
+            Answer::Unsolved | Answer::Error(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(x) => write!(f, "{x}"),
+            Answer::Text(x) | Answer::Grid(x) => f.write_str(x),
+            Answer::Unsolved => f.write_str("✖"),
+            Answer::Error(message) => write!(f, "error: {message}"),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(i128::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Integer)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        if value.trim().contains('\n') {
+            Answer::Grid(value)
+        } else {
+            Answer::Text(value.trim_end().to_string())
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::from(value.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+impl<T: Into<Answer>, E: Display> From<Result<T, E>> for Answer {
+    fn from(value: Result<T, E>) -> Self {
+        match value {
+            Ok(value) => value.into(),
+            Err(e) => Answer::Error(e.to_string()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(Answer::from(42_u64), Answer::Integer(42));
+        assert_eq!(Answer::from(-3_i32), Answer::Integer(-3));
+        assert_eq!(Answer::from(7_usize), Answer::Integer(7));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".into()));
+        assert_eq!(Answer::from("abc\n"), Answer::Text("abc".into()));
+        assert_eq!(Answer::from("#.\n.#"), Answer::Grid("#.\n.#".into()));
+        assert_eq!(Answer::from(Some(1_u8)), Answer::Integer(1));
+        assert_eq!(Answer::from(None::<String>), Answer::Unsolved);
+        assert_eq!(
+            Answer::from(Err::<u64, _>("bad input")),
+            Answer::Error("bad input".into())
+        );
+        assert_eq!(
+            Answer::from(Ok::<_, String>(Some(2_u64))),
+            Answer::Integer(2)
+        );
+    }
+
+    #[test]
+    fn knows_what_is_submittable() {
+        assert!(Answer::Integer(1).is_submittable());
+        assert!(Answer::Text("ABC".into()).is_submittable());
+        assert!(!Answer::Grid("#\n#".into()).is_submittable());
+        assert!(!Answer::Unsolved.is_submittable());
+        assert!(!Answer::Error("x".into()).is_submittable());
+
+        assert!(Answer::Grid("#\n#".into()).is_solved());
+        assert!(!Answer::Error("x".into()).is_solved());
+        assert_eq!(Answer::Error("x".into()).value(), None);
+        assert_eq!(Answer::Integer(-1).value().as_deref(), Some("-1"));
+    }
+}
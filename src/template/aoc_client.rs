@@ -0,0 +1,459 @@
+//! Built-in client for the Advent of Code website.
+//!
+//! Downloads inputs and puzzle descriptions and submits answers using the session cookie.
+//! The base URL is configurable, so the client can be pointed at a local stand-in server.
+
+use std::{env, fmt::Display, fs, io::Read, path::Path, process::Output, time::Duration};
+
+use crate::template::session::Session;
+use crate::template::{aoc_cli, layout, Day};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotSet,
+    BadStatus(u16, String),
+    Transport(String),
+    IO(std::io::Error),
+    Cli(aoc_cli::AocCommandError),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Run `cargo session set` or set AOC_SESSION."
+            ),
+            AocClientError::YearNotSet => write!(f, "AOC_YEAR is not set."),
+            AocClientError::BadStatus(status, url) => {
+                write!(f, "request to \"{url}\" failed with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+            AocClientError::Cli(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for AocClientError {
+    fn from(e: std::io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<aoc_cli::AocCommandError> for AocClientError {
+    fn from(e: aoc_cli::AocCommandError) -> Self {
+        AocClientError::Cli(e)
+    }
+}
+
+/// The program used to talk to the website, selected via `AOC_BACKEND`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// The built-in client (default).
+    Native,
+    /// The external `aoc` executable.
+    AocCli,
+}
+
+impl Backend {
+    pub fn from_env() -> Self {
+        match env::var("AOC_BACKEND").as_deref() {
+            Ok("aoc-cli") => Backend::AocCli,
+            _ => Backend::Native,
+        }
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Backend::Native => write!(f, "native"),
+            Backend::AocCli => write!(f, "aoc-cli"),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .redirects(0)
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+            agent,
+        }
+    }
+
+    /// Create a client from `AOC_BASE_URL`, `AOC_YEAR` and the session cookie.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let year = get_year().ok_or(AocClientError::YearNotSet)?;
+        let session = Session::load().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&base_url, &session.token, year))
+    }
+
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetch the puzzle description as markdown. Contains part two once part one is solved.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(html_to_markdown(&articles(&html).join("\n")))
+    }
+
+    /// Submit an answer and return the response message of the server.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = Self::read_response(response, &url)?;
+        Ok(html_to_markdown(&articles(&html).join("\n")))
+    }
+
+    /// Checks that the website accepts the session cookie.
+    pub fn check_session(&self) -> Result<(), AocClientError> {
+        self.get(&format!("{}/settings", self.base_url)).map(|_| ())
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        Self::read_response(self.request("GET", url).call(), url)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    fn read_response(
+        response: Result<ureq::Response, ureq::Error>,
+        url: &str,
+    ) -> Result<String, AocClientError> {
+        match response {
+            // the website redirects to the login page if the session is invalid.
+            Ok(response) if response.status() >= 300 => Err(AocClientError::BadStatus(
+                response.status(),
+                url.to_string(),
+            )),
+            Ok(response) => {
+                let mut body = String::new();
+                response.into_reader().read_to_string(&mut body)?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(status, _)) => {
+                Err(AocClientError::BadStatus(status, url.to_string()))
+            }
+            Err(e) => Err(AocClientError::Transport(e.to_string())),
+        }
+    }
+}
+
+/// Download the input and puzzle description of a day to the data folder.
+pub fn download(day: Day) -> Result<(), AocClientError> {
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    match Backend::from_env() {
+        Backend::AocCli => {
+            aoc_cli::download(day)?;
+            return Ok(());
+        }
+        Backend::Native => {
+            let client = AocClient::from_env()?;
+            write_data_file(&input_path, &client.input(day)?)?;
+            write_data_file(&puzzle_path, &client.puzzle(day)?)?;
+        }
+    }
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetch the puzzle description of a day, store it and print it.
+pub fn read(day: Day) -> Result<(), AocClientError> {
+    match Backend::from_env() {
+        Backend::AocCli => {
+            aoc_cli::read(day)?;
+        }
+        Backend::Native => {
+            let puzzle = AocClient::from_env()?.puzzle(day)?;
+            write_data_file(&get_puzzle_path(day), &puzzle)?;
+            println!("{puzzle}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Submit an answer and return the response message of the server.
+pub fn submit(day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+    match Backend::from_env() {
+        Backend::AocCli => {
+            let Output { stdout, .. } = aoc_cli::submit(day, part, answer)?;
+            Ok(String::from_utf8_lossy(&stdout).into_owned())
+        }
+        Backend::Native => {
+            let response = AocClient::from_env()?.submit(day, part, answer)?;
+            println!("{response}");
+            Ok(response)
+        }
+    }
+}
+
+/// Checks whether the selected backend is usable and a session cookie is configured.
+pub fn check() -> Result<(), AocClientError> {
+    if Backend::from_env() == Backend::AocCli {
+        aoc_cli::check()?;
+    }
+
+    Session::load()
+        .map(|_| ())
+        .ok_or(AocClientError::SessionNotFound)
+}
+
+fn write_data_file(path: &str, contents: &str) -> Result<(), std::io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_input_path(day: Day) -> String {
+    layout::data_path("inputs", layout::scoped_year(), &format!("{day}.txt"))
+}
+
+fn get_puzzle_path(day: Day) -> String {
+    layout::data_path("puzzles", layout::scoped_year(), &format!("{day}.md"))
+}
+
+fn get_year() -> Option<u16> {
+    env::var("AOC_YEAR").ok()?.parse().ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the contents of all `<article>` elements of a page.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(end) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + end]);
+        rest = &rest[content_start + end..];
+    }
+
+    articles
+}
+
+/// Converts the subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+    let mut href: Option<String> = None;
+
+    while let Some(start) = rest.find('<') {
+        push_text(&mut out, &rest[..start]);
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .find(|s| !s.is_empty())
+            .unwrap_or_default();
+        let is_closing = tag.starts_with('/');
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            // emphasis inside code blocks would corrupt example inputs.
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                href = tag
+                    .split("href=\"")
+                    .nth(1)
+                    .and_then(|s| s.split('"').next())
+                    .map(String::from);
+                out.push('[');
+            }
+            ("a", true) => match href.take() {
+                Some(href) => out.push_str(&format!("]({href})")),
+                None => out.push(']'),
+            },
+            _ => {}
+        }
+    }
+
+    push_text(&mut out, rest);
+
+    out.trim().to_string() + "\n"
+}
+
+fn push_text(out: &mut String, text: &str) {
+    out.push_str(
+        &text
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&"),
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_markdown, AocClient, AocClientError};
+    use crate::day;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Serve a single canned response and return the received request.
+    fn serve_once(status: &str, body: &str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, rx) = serve_once("200 OK", "1\n2\n3\n");
+        let client = AocClient::new(&base_url, "abc\n", 2025);
+
+        assert_eq!(client.input(day!(1)).unwrap(), "1\n2\n3\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2025/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=abc\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, rx) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "abc", 2025);
+
+        assert_eq!(
+            client.submit(day!(12), 2, "42").unwrap(),
+            "That's the right answer!\n"
+        );
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2025/day/12/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn handles_bad_status() {
+        let (base_url, _rx) = serve_once("400 Bad Request", "");
+        let client = AocClient::new(&base_url, "abc", 2025);
+
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::BadStatus(400, _))
+        ));
+    }
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html = r#"<h2>--- Day 1: Test ---</h2><p>Count the <em>elves</em> in <code>a &lt; b</code>, see <a href="/about">here</a>.</p><pre><code>1
+<em>2</em>
+</code></pre><ul><li>one</li><li>two</li></ul>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Test ---\n\nCount the *elves* in `a < b`, see [here](/about).\n\n```\n1\n2\n```\n\n- one\n- two\n"
+        );
+    }
+}
@@ -1,5 +1,35 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    answers::Answers,
+    run_multi::{run_multi, RunOptions},
+    runner::RegisteredDay,
+};
+
+use super::read_or_exit;
+
+pub fn handle(
+    is_release: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    registry: &[RegisteredDay],
+) {
+    let options = RunOptions {
+        is_release,
+        is_timed: false,
+        jobs,
+        timeout,
+    };
+
+    let summary = run_multi(
+        &all_days().collect(),
+        registry,
+        &read_or_exit(Answers::read_from_file()),
+        options,
+    );
+
+    if summary.has_failures() {
+        process::exit(1);
+    }
 }
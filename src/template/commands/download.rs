@@ -1,14 +1,30 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{path::Path, process};
+
+use crate::template::{
+    aoc_client,
+    commands::{examples, refresh_tests},
+    layout, Day,
+};
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if let Err(e) = aoc_client::check() {
+        eprintln!(
+            "{} backend is not usable: {e}",
+            aoc_client::Backend::from_env()
+        );
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = aoc_client::download(day) {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     };
+
+    // fill empty example files from the puzzle description.
+    examples::handle(day, false);
+
+    // fill the tests of a scaffolded day with the example answers.
+    if Path::new(&layout::bin_path(layout::scoped_year(), day)).exists() {
+        refresh_tests::handle(day);
+    }
 }
@@ -0,0 +1,118 @@
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::Path,
+    process,
+};
+
+use crate::template::examples::{Puzzle, PuzzlePart};
+use crate::template::{layout, Day};
+
+/// Extract the examples of a day from its downloaded puzzle description.
+/// Existing, non-empty example files are only replaced with `overwrite`.
+pub fn handle(day: Day, overwrite: bool) {
+    let year = layout::scoped_year();
+    let puzzle_path = layout::data_path("puzzles", year, &format!("{day}.md"));
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Could not read \"{puzzle_path}\". Run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let puzzle = Puzzle::parse(&markdown);
+
+    let Some(part_one) = pick_example(1, &puzzle.part_one) else {
+        println!("No examples found in \"{puzzle_path}\".");
+        return;
+    };
+
+    let mut files = vec![(format!("{day}.txt"), part_one)];
+
+    let part_two = puzzle
+        .part_two
+        .as_ref()
+        .and_then(|part| pick_example(2, part))
+        .filter(|example| *example != part_one);
+
+    // days with a different example per part read them via `read_file_part`.
+    if let Some(part_two) = part_two {
+        files.push((format!("{day}-1.txt"), part_one));
+        files.push((format!("{day}-2.txt"), part_two));
+    }
+
+    for (file_name, example) in files {
+        let path = layout::data_path("examples", year, &file_name);
+        write_example(&path, example, overwrite);
+    }
+
+    for part in 1..=2 {
+        if let Some(answer) = puzzle.part(part).and_then(|p| p.answer.as_deref()) {
+            println!("Expected example answer for part {part}: {answer}");
+        }
+    }
+}
+
+/// Picks the example of a part. If there are several candidates and we run in a terminal, the user can override the pick.
+fn pick_example(part: u8, puzzle_part: &PuzzlePart) -> Option<&str> {
+    let default = puzzle_part.example_index()?;
+
+    if puzzle_part.blocks.len() == 1 || !io::stdin().is_terminal() {
+        return Some(&puzzle_part.blocks[default]);
+    }
+
+    println!(
+        "Found {} code blocks for part {part}:",
+        puzzle_part.blocks.len()
+    );
+
+    for (i, block) in puzzle_part.blocks.iter().enumerate() {
+        let first_line = block.lines().next().unwrap_or_default();
+        let preview: String = first_line.chars().take(40).collect();
+        println!(
+            "  [{}] {} line(s): {preview}{}",
+            i + 1,
+            block.lines().count(),
+            if preview.len() < first_line.len() {
+                "…"
+            } else {
+                ""
+            }
+        );
+    }
+
+    print!("Pick the example for part {part} [{}]: ", default + 1);
+    let _ = io::stdout().flush();
+
+    let mut line = String::new();
+    let _ = io::stdin().read_line(&mut line);
+
+    let index = line
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|i| (1..=puzzle_part.blocks.len()).contains(i))
+        .map_or(default, |i| i - 1);
+
+    Some(&puzzle_part.blocks[index])
+}
+
+fn write_example(path: &str, example: &str, overwrite: bool) {
+    let is_empty = fs::read_to_string(path).map_or(true, |s| s.trim().is_empty());
+
+    if !is_empty && !overwrite {
+        println!("Skipped \"{path}\", it is not empty. Pass `--overwrite` to replace it.");
+        return;
+    }
+
+    if let Some(parent) = Path::new(path).parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    match fs::write(path, example) {
+        Ok(()) => println!("Wrote example to \"{path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write example file \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,6 +1,19 @@
 pub mod all;
 pub mod download;
+pub mod examples;
 pub mod read;
+pub mod refresh_tests;
+pub mod run_day;
 pub mod scaffold;
+pub mod session;
 pub mod solve;
 pub mod time;
+pub mod verify;
+
+/// Unwrap a stored file, or exit if it cannot be read. Commands rather stop than ignore or overwrite a broken file.
+fn read_or_exit<T>(file: Result<T, String>) -> T {
+    file.unwrap_or_else(|e| {
+        eprintln!("Failed to read {e}");
+        std::process::exit(1);
+    })
+}
@@ -1,15 +1,18 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client, Day};
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if let Err(e) = aoc_client::check() {
+        eprintln!(
+            "{} backend is not usable: {e}",
+            aoc_client::Backend::from_env()
+        );
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = aoc_client::read(day) {
+        eprintln!("failed to read day {day}: {e}");
         process::exit(1);
     };
 }
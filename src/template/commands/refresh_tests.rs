@@ -0,0 +1,370 @@
+use std::{fs, path::Path, process};
+
+use crate::template::examples::Puzzle;
+use crate::template::{layout, Day};
+
+const PART_NAMES: [&str; 2] = ["one", "two"];
+
+/// Fill the tests of a day with the example answers of its downloaded puzzle description.
+/// Only placeholder assertions (`assert_eq!(result, None)`) are replaced, tests for further examples are added once.
+pub fn handle(day: Day) {
+    let year = layout::scoped_year();
+    let puzzle_path = layout::data_path("puzzles", year, &format!("{day}.md"));
+    let module_path = layout::bin_path(year, day);
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Could not read \"{puzzle_path}\". Run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let Ok(module) = fs::read_to_string(&module_path) else {
+        eprintln!("Could not read \"{module_path}\". Run `cargo scaffold {day}` first.");
+        process::exit(1);
+    };
+
+    let split_examples = [1, 2].map(|part| {
+        let file_name = format!("{day}-{part}.txt");
+        Path::new(&layout::data_path("examples", year, &file_name)).exists()
+    });
+
+    let (module, changes) = fill_tests(&module, &Puzzle::parse(&markdown), split_examples);
+
+    if changes == 0 {
+        println!("Tests of \"{module_path}\" are up to date.");
+        return;
+    }
+
+    match fs::write(&module_path, module) {
+        Ok(()) => println!("Updated {changes} test(s) in \"{module_path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write module file \"{module_path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns the updated module and the number of changed tests.
+/// `split_examples` tells for each part whether its example lives in `DD-1.txt` / `DD-2.txt`.
+fn fill_tests(module: &str, puzzle: &Puzzle, split_examples: [bool; 2]) -> (String, usize) {
+    let mut module = module.to_string();
+    let mut changes = 0;
+
+    for (part, name) in (1..=2).zip(PART_NAMES) {
+        let Some(puzzle_part) = puzzle.part(part) else {
+            continue;
+        };
+
+        let return_type = return_type(&module, name);
+        let literal = |answer: &str| answer_literal(answer, return_type.as_deref());
+
+        let answer = puzzle_part
+            .examples
+            .first()
+            .and_then(|example| example.answer.as_ref())
+            .or(puzzle_part.answer.as_ref());
+
+        if let Some(range) = fn_body(&module, &format!("test_part_{name}")) {
+            let mut body = module[range.clone()].to_string();
+
+            if let Some(answer) = answer {
+                body = body.replace(
+                    "assert_eq!(result, None);",
+                    &format!("assert_eq!(result, {});", literal(answer)),
+                );
+            }
+
+            if split_examples[usize::from(part - 1)] {
+                body = body
+                    .replace(
+                        "read_file_for_year(\"examples\", YEAR, DAY)",
+                        &format!("read_file_part_for_year(\"examples\", YEAR, DAY, {part})"),
+                    )
+                    .replace(
+                        "read_file(\"examples\", DAY)",
+                        &format!("read_file_part(\"examples\", DAY, {part})"),
+                    );
+            }
+
+            if body != module[range.clone()] {
+                module.replace_range(range, &body);
+                changes += 1;
+            }
+        }
+
+        for (i, example) in puzzle_part.examples.iter().enumerate().skip(1) {
+            let Some(answer) = &example.answer else {
+                continue;
+            };
+
+            let fn_name = format!("test_part_{name}_example_{}", i + 1);
+            if module.contains(&format!("fn {fn_name}()")) {
+                continue;
+            }
+
+            // tests are the last module of the file, add the test before its closing brace.
+            let Some(end) = module.rfind('}') else {
+                continue;
+            };
+
+            let test = format!(
+                "\n    #[test]\n    fn {fn_name}() {{\n        {}\n        assert_eq!(result, {});\n    }}\n",
+                example_call(&module, name, &format!("{:?}", example.input)),
+                literal(answer)
+            );
+
+            module.insert_str(end, &test);
+            changes += 1;
+        }
+    }
+
+    (module, changes)
+}
+
+/// Range of the body of a test function as written by the template, i.e. indented with four spaces.
+fn fn_body(module: &str, fn_name: &str) -> Option<std::ops::Range<usize>> {
+    let start = module.find(&format!("fn {fn_name}()"))?;
+    let end = start + module[start..].find("\n    }")?;
+    Some(start..end)
+}
+
+/// The `let result = ...;` statement of the test of a part, with the example file replaced by `input`.
+/// This keeps calls like `part_one(&parse(...))` of templates with a parse step.
+fn example_call(module: &str, name: &str, input: &str) -> String {
+    let fallback = format!("let result = part_{name}({input});");
+
+    let Some(range) = fn_body(module, &format!("test_part_{name}")) else {
+        return fallback;
+    };
+
+    let body = &module[range];
+    let call = body
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("let result = "));
+
+    let Some(call) = call else {
+        return fallback;
+    };
+
+    let Some(start) = call.find("&advent_of_code::template::read_file") else {
+        return fallback;
+    };
+
+    // find the closing parenthesis of the `read_file` call.
+    let mut depth = 0;
+    for (i, c) in call[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => {
+                let end = start + i + 1;
+                return format!("{}{input}{}", &call[..start], &call[end..]);
+            }
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    fallback
+}
+
+/// The return type of a part as declared in the module, e.g. `Option<u64>`.
+/// Associated types like `Option<Self::PartOne>` are resolved to their definition in the module.
+fn return_type(module: &str, name: &str) -> Option<String> {
+    let start = module.find(&format!("fn part_{name}("))?;
+    let end = start + module[start..].find('{')?;
+    let (_, return_type) = module[start..end].split_once("->")?;
+    let mut return_type = return_type.trim().to_string();
+
+    while let Some(i) = return_type.find("Self::") {
+        let rest = &return_type[i + "Self::".len()..];
+        let len = rest
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+
+        let definition = format!("type {} = ", &rest[..len]);
+        let start = module.find(&definition)? + definition.len();
+        let end = start + module[start..].find(';')?;
+
+        let range = i..i + "Self::".len() + len;
+        return_type.replace_range(range, module[start..end].trim());
+    }
+
+    Some(return_type)
+}
+
+/// An expression for `answer` of the given return type, e.g. `Some(3)` or `Ok("abc".to_string())`.
+/// Without a known type, numeric answers compare with the default `Option<u64>`, other answers with an `Option<String>`.
+fn answer_literal(answer: &str, return_type: Option<&str>) -> String {
+    let Some(return_type) = return_type else {
+        return format!("Some({})", untyped_literal(answer));
+    };
+
+    if let Some(inner) = first_type_arg(return_type, "Option") {
+        return format!("Some({})", answer_literal(answer, Some(inner)));
+    }
+
+    if let Some(inner) = first_type_arg(return_type, "Result") {
+        return format!("Ok({})", answer_literal(answer, Some(inner)));
+    }
+
+    match return_type {
+        "String" => format!("{answer:?}.to_string()"),
+        "&str" | "&'static str" => format!("{answer:?}"),
+        "char" if answer.chars().count() == 1 => answer.chars().map(|c| format!("{c:?}")).collect(),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize"
+            if answer.parse::<i128>().is_ok() =>
+        {
+            answer.to_string()
+        }
+        _ => untyped_literal(answer),
+    }
+}
+
+fn untyped_literal(answer: &str) -> String {
+    if answer.parse::<i64>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}.to_string()")
+    }
+}
+
+/// The first type argument of `wrapper<...>`, e.g. `u64` for `Result<u64, ParseError>` and `Result`.
+fn first_type_arg<'a>(ty: &'a str, wrapper: &str) -> Option<&'a str> {
+    let args = ty
+        .strip_prefix(wrapper)?
+        .trim_start()
+        .strip_prefix('<')?
+        .strip_suffix('>')?;
+
+    let mut depth = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => return Some(args[..i].trim()),
+            _ => {}
+        }
+    }
+
+    Some(args.trim())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_literal, fill_tests, return_type};
+    use crate::template::examples::Puzzle;
+
+    const MODULE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    const PUZZLE: &str = "## --- Day 3: Test ---
+
+For example:
+
+```
+1
+2
+```
+
+The result is `*3*`.
+
+Here is a larger example:
+
+```
+1
+2
+3
+```
+
+This one results in `*6*`.
+
+## --- Part Two ---
+
+Now, for example:
+
+```
+ab
+cd
+```
+
+The result is `*abcd*`.
+";
+
+    #[test]
+    fn fills_placeholder_assertions() {
+        let part_one = &PUZZLE[..PUZZLE.find("## --- Part Two").unwrap()];
+        let (module, changes) = fill_tests(MODULE, &Puzzle::parse(part_one), [false; 2]);
+
+        assert_eq!(changes, 2);
+        assert!(module.contains("assert_eq!(result, Some(3));"));
+        assert!(module.contains(
+            "fn test_part_one_example_2() {\n        let result = part_one(\"1\\n2\\n3\\n\");\n        assert_eq!(result, Some(6));\n    }\n}"
+        ));
+        // part two is still locked.
+        assert_eq!(module.matches("assert_eq!(result, None);").count(), 1);
+    }
+
+    #[test]
+    fn refreshes_once_part_two_unlocks() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        let (module, changes) = fill_tests(MODULE, &puzzle, [true; 2]);
+
+        assert_eq!(changes, 3);
+        assert!(module.contains("assert_eq!(result, Some(\"abcd\".to_string()));"));
+        assert!(module.contains("read_file_part_for_year(\"examples\", YEAR, DAY, 2)"));
+
+        // refreshing again does not touch edited or added tests.
+        let (refreshed, changes) = fill_tests(&module, &puzzle, [true; 2]);
+        assert_eq!(changes, 0);
+        assert_eq!(refreshed, module);
+    }
+
+    #[test]
+    fn keeps_parse_step_of_templates() {
+        let module = MODULE.replace(
+            "part_one(&advent_of_code::template::read_file_for_year(\"examples\", YEAR, DAY))",
+            "part_one(&parse(&advent_of_code::template::read_file_for_year(\"examples\", YEAR, DAY)))",
+        );
+        let (module, _) = fill_tests(&module, &Puzzle::parse(PUZZLE), [false; 2]);
+
+        assert!(module.contains("let result = part_one(&parse(\"1\\n2\\n3\\n\"));"));
+    }
+
+    #[test]
+    fn follows_declared_answer_types() {
+        let module = MODULE.replace("%ANSWER_TYPE%", "String");
+        let (module, _) = fill_tests(&module, &Puzzle::parse(PUZZLE), [false; 2]);
+
+        assert!(module.contains("assert_eq!(result, Some(\"3\".to_string()));"));
+        assert!(module.contains("assert_eq!(result, Some(\"6\".to_string()));"));
+        assert!(module.contains("assert_eq!(result, Some(\"abcd\".to_string()));"));
+    }
+
+    #[test]
+    fn resolves_return_types() {
+        let module = "impl Solution for Day08 {\n    type PartOne = String;\n\n    fn part_one(input: &Self::Parsed) -> Option<Self::PartOne> {\n    }\n\n    fn part_two(input: &Self::Parsed) -> Result<u64, ParseError> {\n    }\n}";
+
+        assert_eq!(
+            return_type(module, "one").as_deref(),
+            Some("Option<String>")
+        );
+        assert_eq!(
+            return_type(module, "two").as_deref(),
+            Some("Result<u64, ParseError>")
+        );
+
+        assert_eq!(
+            answer_literal("42", Some("Option<String>")),
+            "Some(\"42\".to_string())"
+        );
+        assert_eq!(
+            answer_literal("42", Some("Result<u64, ParseError>")),
+            "Ok(42)"
+        );
+        assert_eq!(answer_literal("a", Some("Option<char>")), "Some('a')");
+        assert_eq!(answer_literal("ab", Some("&'static str")), "\"ab\"");
+        assert_eq!(answer_literal("42", None), "Some(42)");
+    }
+}
@@ -0,0 +1,18 @@
+use std::process;
+
+use crate::template::runner::{self, RegisteredDay};
+use crate::template::Day;
+
+/// Runs a registered solution in the current process. Used by `run_multi` to isolate days in separate processes.
+pub fn handle(day: Day, registry: &[RegisteredDay]) {
+    match RegisteredDay::find(registry, day) {
+        Some(solution) => {
+            (solution.run)();
+            runner::exit_on_error();
+        }
+        None => {
+            eprintln!("Day {day} is not registered in this binary.");
+            process::exit(1);
+        }
+    }
+}
@@ -1,14 +1,89 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::examples::Puzzle;
+use crate::template::{commands::refresh_tests, layout, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Named templates, selected with `--template <name>`, live in `src/templates/<name>.txt`.
+const TEMPLATES_DIR: &str = "src/templates";
+
+const DEFAULT_ANSWER_TYPE: &str = "u64";
+
+/// Values for the placeholders of a module template.
+struct Placeholders<'a> {
+    day: Day,
+    year: Option<Year>,
+    title: &'a str,
+    answer_type: &'a str,
+}
+
+/// Replaces `%DAY_NUMBER%` (`9`), `%DAY%` (`09`), `%YEAR%`, `%TITLE%` and `%ANSWER_TYPE%`.
+fn render(template: &str, placeholders: &Placeholders) -> String {
+    let year = placeholders
+        .year
+        .map(|year| year.to_string())
+        .unwrap_or_default();
+
+    template
+        .replace("%DAY_NUMBER%", &placeholders.day.into_inner().to_string())
+        .replace("%DAY%", &placeholders.day.to_string())
+        .replace("%YEAR%", &year)
+        .replace("%TITLE%", placeholders.title)
+        .replace("%ANSWER_TYPE%", placeholders.answer_type)
+}
+
+fn read_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.to_string());
+    };
+
+    fs::read_to_string(format!("{TEMPLATES_DIR}/{name}.txt")).map_err(|e| {
+        let names = template_names();
+        if names.is_empty() {
+            format!("Failed to read template \"{name}\": {e}")
+        } else {
+            format!(
+                "Failed to read template \"{name}\": {e}. Available templates: {}.",
+                names.join(", ")
+            )
+        }
+    })
+}
+
+/// Names of the templates in the templates directory, in alphabetical order.
+fn template_names() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let is_template = path.extension().is_some_and(|ext| ext == "txt");
+                    is_template.then(|| path.file_stem()?.to_str().map(str::to_string))?
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+    names
+}
+
+/// The title of a downloaded puzzle description, or `Day <day>`.
+fn puzzle_title(year: Option<Year>, day: Day) -> String {
+    fs::read_to_string(layout::data_path("puzzles", year, &format!("{day}.md")))
+        .ok()
+        .and_then(|markdown| Puzzle::parse(&markdown).title)
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()))
+}
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -19,18 +94,66 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty data file. Returns `false` if the file exists and has content, e.g. a downloaded input.
+fn create_data_file(path: &str) -> Result<bool, std::io::Error> {
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(false);
+    }
+
+    File::create(path)?;
+    Ok(true)
+}
+
+/// Cargo only discovers `src/bin/DD.rs` by itself, bins in year directories need a `[[bin]]` entry.
+fn register_year_bin(year: Year, day: Day) -> Result<bool, std::io::Error> {
+    let manifest = fs::read_to_string("Cargo.toml")?;
+    let name = layout::bin_name(Some(year), day);
+
+    if manifest.contains(&format!("name = \"{name}\"")) {
+        return Ok(false);
+    }
+
+    let mut file = OpenOptions::new().append(true).open("Cargo.toml")?;
+    write!(
+        file,
+        "\n[[bin]]\nname = \"{name}\"\npath = \"{}\"\n",
+        layout::bin_path(Some(year), day)
+    )?;
+
+    Ok(true)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Scaffold a day. If `year` is passed or the current year has a year directory, the files are created in `YYYY/` subdirectories.
+/// The module is created from the named `template`, or `src/template.txt`.
+pub fn handle(
+    day: Day,
+    overwrite: bool,
+    year: Option<Year>,
+    template: Option<&str>,
+    answer_type: Option<&str>,
+) {
+    let year = year.or_else(layout::scoped_year);
+
+    let template = match read_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = layout::data_path("inputs", year, &format!("{day}.txt"));
+    let example_path = layout::data_path("examples", year, &format!("{day}.txt"));
+    let module_path = layout::bin_path(year, day);
+
+    for path in [&input_path, &example_path, &module_path] {
+        if let Some(parent) = Path::new(path).parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                eprintln!("Failed to create directory \"{}\": {e}", parent.display());
+                process::exit(1);
+            }
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +163,14 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let placeholders = Placeholders {
+        day,
+        year: year.or_else(Year::current),
+        title: &puzzle_title(year, day),
+        answer_type: answer_type.unwrap_or(DEFAULT_ANSWER_TYPE),
+    };
+
+    match file.write_all(render(&template, &placeholders).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -54,26 +180,77 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
+    match create_data_file(&input_path) {
+        Ok(true) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Ok(false) => {
+            println!("Kept existing input file \"{}\"", &input_path);
+        }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
+    match create_data_file(&example_path) {
+        Ok(true) => {
             println!("Created empty example file \"{}\"", &example_path);
         }
+        Ok(false) => {
+            println!("Kept existing example file \"{}\"", &example_path);
+        }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
     }
 
+    if let Some(year) = year {
+        match register_year_bin(year, day) {
+            Ok(true) => println!("Registered binary in \"Cargo.toml\""),
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to register binary in \"Cargo.toml\": {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    // the puzzle may have been downloaded before, e.g. when scaffolding again with `--overwrite`.
+    if Path::new(&layout::data_path("puzzles", year, &format!("{day}.md"))).exists() {
+        refresh_tests::handle(day);
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Placeholders};
+    use crate::{day, template::Year};
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(9),
+            year: Year::new(2024),
+            title: "Disk Fragmenter",
+            answer_type: "String",
+        };
+
+        assert_eq!(
+            render(
+                "// %TITLE% (%YEAR%/%DAY%)\nsolution!(%DAY_NUMBER%);\nfn part_one() -> Option<%ANSWER_TYPE%>",
+                &placeholders
+            ),
+            "// Disk Fragmenter (2024/09)\nsolution!(9);\nfn part_one() -> Option<String>"
+        );
+    }
 }
@@ -0,0 +1,98 @@
+use std::{io, process};
+
+use crate::template::aoc_client::{self, AocClient, AocClientError, Backend};
+use crate::template::session::{self, Session};
+
+pub enum Action {
+    /// Store a token. Read from stdin if not passed as argument, to keep it out of the shell history.
+    Set(Option<String>),
+    Show,
+    Check,
+}
+
+pub fn handle(action: Action) {
+    match action {
+        Action::Set(token) => set(token),
+        Action::Show => show(),
+        Action::Check => check(),
+    }
+}
+
+fn set(token: Option<String>) {
+    let token = token.unwrap_or_else(|| {
+        println!("Paste your session cookie and press enter:");
+        let mut line = String::new();
+        if let Err(e) = io::stdin().read_line(&mut line) {
+            eprintln!("failed to read session cookie: {e}");
+            process::exit(1);
+        }
+        line
+    });
+
+    let token = token.trim();
+
+    if let Err(e) = session::validate(token) {
+        eprintln!("Invalid session cookie: {e}");
+        process::exit(1);
+    }
+
+    match session::store(token) {
+        Ok(path) => println!("🎄 Stored session cookie in \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("failed to store session cookie: {e}");
+            process::exit(1);
+        }
+    }
+
+    if std::env::var("AOC_SESSION").is_ok() {
+        println!("Note: AOC_SESSION is set and takes precedence over the stored session cookie.");
+    }
+}
+
+fn show() {
+    println!("Backend: {}", Backend::from_env());
+
+    match session::config_path() {
+        Some(path) => println!("Config file: \"{}\"", path.display()),
+        None => println!("Config file: could not determine config directory"),
+    }
+
+    match Session::load() {
+        Some(session) => {
+            println!("Session: {} (from {})", session.masked(), session.source);
+        }
+        None => println!("Session: not configured. Run `cargo session set` to store one."),
+    }
+}
+
+fn check() {
+    show();
+    println!("---");
+
+    let Some(session) = Session::load() else {
+        eprintln!("No session cookie configured.");
+        process::exit(1);
+    };
+
+    if let Err(e) = session::validate(&session.token) {
+        eprintln!("Invalid session cookie: {e}");
+        process::exit(1);
+    }
+
+    if let Err(e) = aoc_client::check() {
+        eprintln!("{} backend is not usable: {e}", Backend::from_env());
+        process::exit(1);
+    }
+
+    match AocClient::from_env().and_then(|client| client.check_session()) {
+        Ok(()) => println!("🎄 Session cookie is valid."),
+        Err(e @ AocClientError::BadStatus(..)) => {
+            eprintln!("Session cookie was not accepted, it might have expired: {e}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to check session cookie: {e}");
+            process::exit(1);
+        }
+    }
+}
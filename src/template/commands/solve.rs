@@ -1,9 +1,10 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{layout, Day};
 
 pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    let bin_name = layout::bin_name(layout::scoped_year(), day);
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name];
 
     if dhat {
         cmd_args.extend([
@@ -30,5 +31,9 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // a part that returned an error or a panic fails the solution, pass that on to the caller.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
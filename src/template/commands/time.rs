@@ -1,11 +1,22 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 
-use crate::template::run_multi::run_multi;
+use crate::template::answers::Answers;
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::runner::RegisteredDay;
 use crate::template::timings::Timings;
 use crate::template::{all_days, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+use super::read_or_exit;
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    registry: &[RegisteredDay],
+) {
+    let stored_timings = read_or_exit(Timings::read_from_file());
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,19 +32,33 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        jobs,
+        timeout,
+    };
+
+    let timings = run_multi(
+        &days_to_run,
+        registry,
+        &read_or_exit(Answers::read_from_file()),
+        options,
+    )
+    .timings
+    .unwrap_or_default();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update() {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
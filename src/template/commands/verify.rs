@@ -0,0 +1,105 @@
+use std::{collections::HashSet, process, time::Duration};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{run_multi, DayStatus, RunOptions};
+use crate::template::runner::RegisteredDay;
+use crate::template::submissions::Submissions;
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+
+use super::read_or_exit;
+
+/// Run days and compare their answers against the known answers in `data/answers.json`.
+/// With `record`, answers of parts without a known answer are added to the file,
+/// as long as `data/submissions.json` shows that they were accepted.
+pub fn handle(
+    day: Option<Day>,
+    record: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    registry: &[RegisteredDay],
+) {
+    let mut answers = read_or_exit(Answers::read_from_file());
+
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || {
+            if record {
+                all_days().collect()
+            } else {
+                // without `--record`, only days with known answers can be verified.
+                all_days().filter(|day| answers.has_day(*day)).collect()
+            }
+        },
+        |day| HashSet::from([day]),
+    );
+
+    if days_to_run.is_empty() {
+        println!("No known answers to verify. Run `cargo verify --record` to record the current answers.");
+        return;
+    }
+
+    let options = RunOptions {
+        is_release: true,
+        is_timed: false,
+        jobs,
+        timeout,
+    };
+
+    let summary = run_multi(&days_to_run, registry, &answers, options);
+
+    let checked = summary
+        .results
+        .iter()
+        .flat_map(|result| (1..=2).filter(|part| answers.get(result.day, *part).is_some()))
+        .count();
+    let mismatches: usize = summary.results.iter().map(|r| r.mismatches.len()).sum();
+
+    println!();
+    println!("{ANSI_BOLD}Verified {checked} part(s), {mismatches} mismatch(es).{ANSI_RESET}");
+
+    if record {
+        let submissions = read_or_exit(Submissions::read_from_file());
+        let mut recorded = 0;
+        let mut unconfirmed = vec![];
+
+        for result in summary.results.iter().filter(|r| r.status == DayStatus::Ok) {
+            for part in 1..=2 {
+                if answers.get(result.day, part).is_some() {
+                    continue;
+                }
+
+                let Some(answer) = result.answer(part) else {
+                    continue;
+                };
+
+                // only record answers that are known to be right.
+                if submissions.accepted(result.day, part) == Some(answer) {
+                    answers.set(result.day, part, answer);
+                    recorded += 1;
+                } else {
+                    unconfirmed.push(format!("day {} part {part}", result.day));
+                }
+            }
+        }
+
+        if recorded > 0 {
+            match answers.store_file() {
+                Ok(()) => println!("Recorded {recorded} new answer(s)."),
+                Err(e) => {
+                    eprintln!("Failed to store answers: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+
+        if !unconfirmed.is_empty() {
+            println!(
+                "Not recorded, as they were not accepted yet: {}. Submit them with `cargo solve <day> --submit <part>`.",
+                unconfirmed.join(", ")
+            );
+        }
+    }
+
+    if summary.has_failures() {
+        process::exit(1);
+    }
+}
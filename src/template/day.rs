@@ -2,13 +2,23 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::year;
+#[cfg(feature = "today")]
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
+/// The most puzzles an event has.
+pub const MAX_DAYS: u8 = 25;
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Events starting 2025 have 12 days. The days of such an event are enumerated up to the 12th, see [`all_days`],
+/// and the command line rejects later days. Parsing stays independent of the event, so stored data of earlier
+/// events keeps its days 13 to 25.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +35,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAYS {
             return None;
         }
         Some(Self(day))
@@ -45,12 +55,15 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of this year's event in december, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        let day = u8::try_from(today.day()).ok()?;
+
+        if today.month() == 12 && day <= year.day_count() {
+            Some(Self(day))
         } else {
             None
         }
@@ -94,26 +107,34 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {MAX_DAYS}")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the current event, see [`crate::template::Year::current`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of an event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::up_to(year::day_count())
+    }
+
+    pub(crate) fn up_to(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(MAX_DAYS),
+        }
     }
 }
 
@@ -121,7 +142,7 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
         // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
@@ -135,6 +156,7 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// As the year is only known at runtime, this accepts every day up to the 25th.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
@@ -154,11 +176,12 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{AllDays, Day};
+    use crate::template::Year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::up_to(25);
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +210,22 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn validates_days_independent_of_the_event() {
+        assert_eq!(Day::new(0), None);
+        assert_eq!(Day::new(13), Some(Day(13)));
+        assert_eq!(Day::new(25), Some(Day(25)));
+        assert_eq!(Day::new(26), None);
+        assert!("26".parse::<Day>().is_err());
+    }
+
+    #[test]
+    fn all_days_iterator_for_short_events() {
+        let days: Vec<Day> = Year::new(2025).unwrap().days().collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,350 @@
+//! Extracts example inputs and their expected answers from puzzle descriptions.
+//!
+//! Puzzle descriptions are markdown files as written by `download`. Examples are code blocks, either fenced
+//! (```` ``` ````) or raw `<pre><code>` HTML. Expected answers are emphasized inline code, e.g. `` `*42*` ``.
+
+/// An example input together with the answer the puzzle gives for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answer: Option<String>,
+}
+
+/// A section of the puzzle description, i.e. part one or part two.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzlePart {
+    /// Every code block of the section, in order of appearance.
+    pub blocks: Vec<String>,
+    /// The last emphasized value of the section, which usually is the answer for the example.
+    pub answer: Option<String>,
+    /// The examples of the section. The first one is the pick of [`PuzzlePart::example_index`], further
+    /// ones are multi-line blocks introduced by a paragraph that mentions an example.
+    pub examples: Vec<Example>,
+}
+
+impl PuzzlePart {
+    /// Heuristic pick of the example input: the first block that spans multiple lines, or the first block.
+    pub fn example_index(&self) -> Option<usize> {
+        example_index(&self.blocks)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The title of the puzzle, e.g. `Secret Entrance`.
+    pub title: Option<String>,
+    pub part_one: PuzzlePart,
+    /// Only present once part one has been solved.
+    pub part_two: Option<PuzzlePart>,
+}
+
+impl Puzzle {
+    pub fn parse(markdown: &str) -> Self {
+        // NOTE: part two starts with a `--- Part Two ---` heading.
+        let (part_one, part_two) = match markdown.find("--- Part Two ---") {
+            Some(index) => (&markdown[..index], Some(&markdown[index..])),
+            None => (markdown, None),
+        };
+
+        Puzzle {
+            title: part_one.lines().find_map(parse_title),
+            part_one: parse_part(part_one),
+            part_two: part_two.map(parse_part),
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PuzzlePart> {
+        match part {
+            1 => Some(&self.part_one),
+            2 => self.part_two.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a title line like `## --- Day 1: Secret Entrance ---`.
+fn parse_title(line: &str) -> Option<String> {
+    let title = line.trim_start_matches('#').trim();
+    let title = title.strip_prefix("---")?.strip_suffix("---")?.trim();
+    let (_, name) = title.split_once(':')?;
+    Some(name.trim().to_string())
+}
+
+fn example_index(blocks: &[String]) -> Option<usize> {
+    blocks
+        .iter()
+        .position(|block| block.lines().count() > 1)
+        .or_else(|| (!blocks.is_empty()).then_some(0))
+}
+
+fn parse_part(markdown: &str) -> PuzzlePart {
+    let segments = segments(markdown);
+
+    let blocks: Vec<String> = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Block(block) => Some(block.clone()),
+            Segment::Text(_) => None,
+        })
+        .collect();
+
+    let text: String = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(text) => Some(text.as_str()),
+            Segment::Block(_) => None,
+        })
+        .collect();
+
+    PuzzlePart {
+        examples: pair_examples(&segments, example_index(&blocks)),
+        answer: emphasized_values(&text).into_iter().last(),
+        blocks,
+    }
+}
+
+/// Prose or a code block of a puzzle description.
+enum Segment {
+    Text(String),
+    Block(String),
+}
+
+/// Pairs example blocks with the last emphasized value that follows them, up to the next example.
+fn pair_examples(segments: &[Segment], first: Option<usize>) -> Vec<Example> {
+    let Some(first) = first else {
+        return vec![];
+    };
+
+    let mut examples: Vec<Example> = vec![];
+    let mut block_index = 0;
+    let mut previous_text = "";
+
+    for segment in segments {
+        match segment {
+            Segment::Block(block) => {
+                // further examples are usually introduced with "Here is a larger example:" or similar.
+                let is_example = block_index == first
+                    || (block_index > first
+                        && block.lines().count() > 1
+                        && previous_text.to_lowercase().contains("example"));
+
+                if is_example {
+                    examples.push(Example {
+                        input: block.clone(),
+                        answer: None,
+                    });
+                }
+
+                block_index += 1;
+                previous_text = "";
+            }
+            Segment::Text(text) => {
+                if let Some(example) = examples.last_mut() {
+                    if let Some(answer) = emphasized_values(text).pop() {
+                        example.answer = Some(answer);
+                    }
+                }
+                previous_text = text;
+            }
+        }
+    }
+
+    examples
+}
+
+/// Splits markdown into prose and the content of code blocks, fenced or `<pre><code>`.
+fn segments(markdown: &str) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut text = String::new();
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        let block = if trimmed.starts_with("```") {
+            let content: Vec<&str> = lines
+                .by_ref()
+                .take_while(|l| !l.trim().starts_with("```"))
+                .collect();
+            content.join("\n")
+        } else if let Some(start) = trimmed.strip_prefix("<pre><code>") {
+            let mut content = String::new();
+            let mut current = start;
+
+            loop {
+                if let Some(end) = current.find("</code></pre>") {
+                    content.push_str(&current[..end]);
+                    break;
+                }
+                content.push_str(current);
+                content.push('\n');
+                match lines.next() {
+                    Some(next) => current = next,
+                    None => break,
+                }
+            }
+
+            decode_html(&content)
+        } else {
+            text.push_str(line);
+            text.push('\n');
+            continue;
+        };
+
+        if block.trim().is_empty() {
+            continue;
+        }
+
+        segments.push(Segment::Text(std::mem::take(&mut text)));
+        segments.push(Segment::Block(if block.ends_with('\n') {
+            block
+        } else {
+            block + "\n"
+        }));
+    }
+
+    segments.push(Segment::Text(text));
+    segments
+}
+
+/// Returns emphasized inline code values: `` `*42*` ``, `` *`42`* `` or `<code><em>42</em></code>`.
+fn emphasized_values(text: &str) -> Vec<String> {
+    let patterns = [
+        ("`*", "*`"),
+        ("*`", "`*"),
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ];
+
+    let mut values = vec![];
+    let mut rest = text;
+
+    // scan left to right, so the closing delimiter of a value is never taken as an opening one.
+    while !rest.is_empty() {
+        let value = patterns.iter().find_map(|(open, close)| {
+            let start = rest.strip_prefix(open)?;
+            let end = start.find(close)?;
+            let value = &start[..end];
+            let is_valid = !value.is_empty() && !value.contains(['\n', '`']);
+            is_valid.then(|| (value, open.len() + end + close.len()))
+        });
+
+        match value {
+            Some((value, len)) => {
+                values.push(decode_html(value));
+                rest = &rest[len..];
+            }
+            None => {
+                let next = rest.chars().next().map_or(1, char::len_utf8);
+                rest = &rest[next..];
+            }
+        }
+    }
+
+    values
+}
+
+fn decode_html(text: &str) -> String {
+    text.replace("<em>", "")
+        .replace("</em>", "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Puzzle, PuzzlePart};
+
+    fn example(part: &PuzzlePart) -> Option<&str> {
+        part.example_index().map(|i| part.blocks[i].as_str())
+    }
+
+    const PUZZLE: &str = "## --- Day 1: Secret Entrance ---
+
+The safe has a dial with `0` through `99`. For example:
+
+```
+L68
+L30
+R48
+```
+
+Following these rotations, the dial points at `0` a total of `*3*` times.
+
+## --- Part Two ---
+
+Count every click instead. For example:
+
+```
+L68
+R1000
+```
+
+In this example, the dial points at `0` a total of `*6*` times.
+";
+
+    #[test]
+    fn parses_examples_and_answers() {
+        let puzzle = Puzzle::parse(PUZZLE);
+
+        assert_eq!(puzzle.title.as_deref(), Some("Secret Entrance"));
+        assert_eq!(example(&puzzle.part_one), Some("L68\nL30\nR48\n"));
+        assert_eq!(puzzle.part_one.answer.as_deref(), Some("3"));
+
+        let part_two = puzzle.part_two.as_ref().unwrap();
+        assert_eq!(example(part_two), Some("L68\nR1000\n"));
+        assert_eq!(part_two.answer.as_deref(), Some("6"));
+    }
+
+    #[test]
+    fn handles_missing_part_two() {
+        let puzzle = Puzzle::parse(&PUZZLE[..PUZZLE.find("## --- Part Two").unwrap()]);
+        assert_eq!(puzzle.part_two, None);
+    }
+
+    #[test]
+    fn parses_html_blocks() {
+        let puzzle = Puzzle::parse(
+            "<p>For example:</p>\n<pre><code>1 &lt; 2\n<em>3</em>\n</code></pre>\n<p>The result is <code><em>42</em></code>.</p>",
+        );
+
+        assert_eq!(example(&puzzle.part_one), Some("1 < 2\n3\n"));
+        assert_eq!(puzzle.part_one.answer.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn prefers_multi_line_blocks() {
+        let puzzle = Puzzle::parse("```\nabc\n```\n\nthen\n\n```\n1\n2\n```\n");
+        assert_eq!(puzzle.part_one.blocks.len(), 2);
+        assert_eq!(puzzle.part_one.example_index(), Some(1));
+    }
+
+    #[test]
+    fn ignores_emphasis_in_code_blocks() {
+        let puzzle = Puzzle::parse("```\n`*1*`\n```\n\nresult `*2*`, then *`3`* and `4`.\n");
+        assert_eq!(puzzle.part_one.answer.as_deref(), Some("3"));
+
+        let puzzle = Puzzle::parse("`*2*` times, `*3*` times\n");
+        assert_eq!(puzzle.part_one.answer.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn pairs_examples_with_answers() {
+        let puzzle = Puzzle::parse(
+            "For example:\n\n```\n1\n2\n```\n\nAfter one step:\n\n```\n3\n4\n```\n\nThe result is `*7*`.\n\n\
+            Here is a larger example:\n\n```\n5\n6\n7\n```\n\nThis one results in `*18*`.\n",
+        );
+
+        let examples = &puzzle.part_one.examples;
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, "1\n2\n");
+        assert_eq!(examples[0].answer.as_deref(), Some("7"));
+        assert_eq!(examples[1].input, "5\n6\n7\n");
+        assert_eq!(examples[1].answer.as_deref(), Some("18"));
+    }
+}
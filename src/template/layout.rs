@@ -0,0 +1,127 @@
+//! Locations of solution and data files.
+//!
+//! A repository can hold solutions for several events. The days of a year live in `YYYY/` subdirectories
+//! (`src/bin/2024/01.rs`, `data/inputs/2024/01.txt`) once `src/bin/YYYY/` exists. Otherwise, days use the
+//! flat layout (`src/bin/01.rs`, `data/inputs/01.txt`) and belong to the year configured via `AOC_YEAR`.
+
+use std::{
+    fs::{self, File},
+    io,
+    path::Path,
+};
+
+use crate::template::{Day, Year};
+
+/// Returns the current year if its days live in a year directory.
+pub fn scoped_year() -> Option<Year> {
+    Year::current().filter(|year| is_year_scoped(*year))
+}
+
+pub fn is_year_scoped(year: Year) -> bool {
+    Path::new("src").join("bin").join(year.to_string()).is_dir()
+}
+
+/// Name of the binary of a day, e.g. `01` or `2024-01`.
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) => format!("{year}-{day}"),
+        None => day.to_string(),
+    }
+}
+
+/// Path of the module of a day, e.g. `src/bin/01.rs` or `src/bin/2024/01.rs`.
+pub fn bin_path(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) => format!("src/bin/{year}/{day}.rs"),
+        None => format!("src/bin/{day}.rs"),
+    }
+}
+
+/// Path of a data file of a day, e.g. `data/inputs/01.txt` or `data/inputs/2024/01.txt`.
+pub fn data_path(folder: &str, year: Option<Year>, file_name: &str) -> String {
+    match year {
+        Some(year) => format!("data/{folder}/{year}/{file_name}"),
+        None => format!("data/{folder}/{file_name}"),
+    }
+}
+
+/// Path of a file that is stored once per year, e.g. `data/timings.json` or `data/2024/timings.json`.
+pub fn store_path(year: Option<Year>, file_name: &str) -> String {
+    match year {
+        Some(year) => format!("data/{year}/{file_name}"),
+        None => format!("data/{file_name}"),
+    }
+}
+
+/// Create or truncate a file that is stored once per year, creating its directory if needed.
+pub fn create_store_file(year: Option<Year>, file_name: &str) -> Result<File, io::Error> {
+    let path = store_path(year, file_name);
+
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    File::create(path)
+}
+
+/// Read and parse a file that is stored once per year. A file that does not exist yet yields the default value.
+/// Errors name the file, so that a broken file is reported instead of being dropped.
+pub fn read_store_file<T: Default>(
+    year: Option<Year>,
+    file_name: &str,
+    parse: impl FnOnce(String) -> Result<T, String>,
+) -> Result<T, String> {
+    let path = store_path(year, file_name);
+
+    match fs::read_to_string(&path) {
+        Ok(content) => parse(content).map_err(|e| format!("`{path}`: {e}")),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(format!("`{path}`: {e}")),
+    }
+}
+
+/// Returns every year that has a year directory, in ascending order.
+pub fn scoped_years() -> Vec<Year> {
+    let mut years: Vec<Year> = Path::new("src")
+        .join("bin")
+        .read_dir()
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    years.sort_unstable();
+    years
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_name, bin_path, data_path, store_path};
+    use crate::{day, template::Year};
+
+    #[test]
+    fn builds_flat_paths() {
+        assert_eq!(bin_name(None, day!(1)), "01");
+        assert_eq!(bin_path(None, day!(1)), "src/bin/01.rs");
+        assert_eq!(data_path("inputs", None, "01.txt"), "data/inputs/01.txt");
+        assert_eq!(store_path(None, "timings.json"), "data/timings.json");
+    }
+
+    #[test]
+    fn builds_year_paths() {
+        let year = Year::new(2024);
+        assert_eq!(bin_name(year, day!(1)), "2024-01");
+        assert_eq!(bin_path(year, day!(1)), "src/bin/2024/01.rs");
+        assert_eq!(
+            data_path("inputs", year, "01.txt"),
+            "data/inputs/2024/01.txt"
+        );
+        assert_eq!(store_path(year, "timings.json"), "data/2024/timings.json");
+    }
+}
@@ -1,15 +1,30 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod ocr;
 pub mod runner;
 
+pub use answer::Answer;
 pub use day::*;
+pub use solution::Solution;
+pub use year::*;
 
+mod answer;
+mod answers;
 mod day;
+mod examples;
+mod layout;
+mod protocol;
 mod readme_benchmarks;
 mod run_multi;
+mod session;
+mod solution;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -18,27 +33,51 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_file_for_year(folder, None, day)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_file_part_for_year(folder, None, day, part)
+}
+
+/// Same as [`read_file`], for a solution that lives in a year directory, e.g. `data/examples/2024/01.txt`.
+/// Solutions pass the `YEAR` constant defined by [`solution`].
+#[must_use]
+pub fn read_file_for_year(folder: &str, year: Option<Year>, day: Day) -> String {
+    read_data_file(folder, year, &format!("{day}.txt"))
+}
+
+/// Same as [`read_file_part`], for a solution that lives in a year directory.
+#[must_use]
+pub fn read_file_part_for_year(folder: &str, year: Option<Year>, day: Day, part: u8) -> String {
+    read_data_file(folder, year, &format!("{day}-{part}.txt"))
+}
+
+fn read_data_file(folder: &str, year: Option<Year>, file_name: &str) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(layout::data_path(folder, year, file_name));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
+///
+/// `YEAR` is set if the solution lives in a year directory, e.g. `src/bin/2024/01.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A `parse = <function>` parameter adds a separately timed parse step. The function receives the
+/// input as `&str`, and both parts receive a reference to its return value instead of the input.
+/// With `try_parse = <function>`, the function returns a `Result`: the parts receive a reference to the
+/// `Ok` value, an `Err` fails the solution and skips the parts.
+///
+/// A `solve = <function>` parameter replaces both parts with a single function that returns a tuple
+/// of both answers. It is timed as one run, e.g. `solution!(7, solve = solve)` or
+/// `solution!(7, parse = parse, solve = solve)`.
+///
+/// Instead of free functions, a type implementing [`Solution`] can be passed, e.g. `solution!(8, Day08)`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +89,113 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $crate::template::runner::infallible($parse), [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $crate::template::runner::infallible($parse), [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $crate::template::runner::infallible($parse), [part_two, 2]);
+    };
+    ($day:expr, try_parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, try_parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, try_parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
+    ($day:expr, solve = $solve:expr) => {
+        $crate::solution!(@header $day);
+
+        // Not part of the public API, used by `main` and the in-process runner.
+        #[doc(hidden)]
+        pub fn __run() {
+            let input = $crate::template::read_file_for_year("inputs", YEAR, DAY);
+            $crate::template::runner::run_solve($solve, input.as_str(), DAY);
+        }
+    };
+    ($day:expr, parse = $parse:expr, solve = $solve:expr) => {
+        $crate::solution!(@header $day);
+
+        // Not part of the public API, used by `main` and the in-process runner.
+        #[doc(hidden)]
+        pub fn __run() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file_for_year("inputs", YEAR, DAY);
+            if let Some(parsed) = run_parse(infallible($parse), &input) {
+                run_solve($solve, &parsed, DAY);
+            }
+        }
+    };
+    ($day:expr, try_parse = $parse:expr, solve = $solve:expr) => {
+        $crate::solution!(@header $day);
+
+        // Not part of the public API, used by `main` and the in-process runner.
+        #[doc(hidden)]
+        pub fn __run() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file_for_year("inputs", YEAR, DAY);
+            if let Some(parsed) = run_parse($parse, &input) {
+                run_solve($solve, &parsed, DAY);
+            }
+        }
+    };
+    ($day:expr, $solution:ident) => {
+        $crate::solution!(@header $day);
+
+        // Not part of the public API, used by `main` and the in-process runner.
+        #[doc(hidden)]
+        pub fn __run() {
+            let input = $crate::template::read_file_for_year("inputs", YEAR, DAY);
+            $crate::template::runner::run_solution::<$solution>(&input, DAY);
+        }
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        // Not part of the public API, used by `main` and the in-process runner.
+        #[doc(hidden)]
+        pub fn __run() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file_for_year("inputs", YEAR, DAY);
+            $( run_part($func, &input, DAY, $part); )*
+        }
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        // Not part of the public API, used by `main` and the in-process runner.
+        #[doc(hidden)]
+        pub fn __run() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file_for_year("inputs", YEAR, DAY);
+            if let Some(parsed) = run_parse($parse, &input) {
+                $( run_part($func, &parsed, DAY, $part); )*
+            }
+        }
+    };
+
+    (@header $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The year of the current day, if the solution lives in a year directory.
+        #[allow(dead_code)]
+        const YEAR: Option<$crate::template::Year> =
+            $crate::template::Year::from_source_path(file!());
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            __run();
+            $crate::template::runner::exit_on_error();
         }
     };
 }
@@ -0,0 +1,243 @@
+//! Reads capital letters drawn with `#` and `.`, as rendered by several puzzles.
+//!
+//! Two fonts are known: letters that are 6 rows high (4 columns wide) and letters that are 10 rows high
+//! (6 columns wide). Any character other than `.` and whitespace counts as a lit pixel.
+
+/// Glyphs of the 6 rows high font, trimmed to their lit columns.
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Glyphs of the 10 rows high font, trimmed to their lit columns.
+const FONT_10: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads the letters of a rendered grid. Returns `None` if the grid has an unknown height or
+/// contains a glyph that is not part of the font.
+///
+/// ```
+/// # use advent_of_code::template::ocr;
+/// let grid = "#..#.###.\n#..#..#..\n####..#..\n#..#..#..\n#..#..#..\n#..#.###.";
+/// assert_eq!(ocr::recognize(grid).as_deref(), Some("HI"));
+/// ```
+pub fn recognize(grid: &str) -> Option<String> {
+    let rows = pixel_rows(grid);
+
+    let font = match rows.len() {
+        6 => FONT_6,
+        10 => FONT_10,
+        _ => return None,
+    };
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let is_blank_column = |x: usize| rows.iter().all(|row| !row.get(x).copied().unwrap_or(false));
+
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        // letters are usually separated by blank columns, but wide letters like `Y` can touch the next one.
+        // match the glyphs of a run of lit columns one after another.
+        let end = (x..width).find(|x| is_blank_column(*x)).unwrap_or(width);
+
+        while x < end {
+            let (letter, glyph_width) = font.iter().find_map(|(letter, pattern)| {
+                let glyph_width = pattern.lines().next()?.len();
+                let is_match =
+                    x + glyph_width <= end && render_glyph(&rows, x..x + glyph_width) == *pattern;
+                is_match.then_some((*letter, glyph_width))
+            })?;
+
+            letters.push(letter);
+            x += glyph_width;
+        }
+    }
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+/// Converts the grid into rows of lit pixels, dropping blank rows above and below the letters.
+fn pixel_rows(grid: &str) -> Vec<Vec<bool>> {
+    let rows: Vec<Vec<bool>> = grid
+        .lines()
+        .map(|line| {
+            line.trim_end()
+                .chars()
+                .map(|c| c != '.' && !c.is_whitespace())
+                .collect()
+        })
+        .collect();
+
+    let is_lit = |row: &Vec<bool>| row.iter().any(|pixel| *pixel);
+    let Some(first) = rows.iter().position(is_lit) else {
+        return vec![];
+    };
+    let last = rows.iter().rposition(is_lit).unwrap_or(first);
+
+    rows[first..=last].to_vec()
+}
+
+fn render_glyph(rows: &[Vec<bool>], columns: std::ops::Range<usize>) -> String {
+    rows.iter()
+        .map(|row| {
+            columns
+                .clone()
+                .map(|x| {
+                    if row.get(x).copied().unwrap_or(false) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{recognize, FONT_10, FONT_6};
+
+    /// Renders letters of a font next to each other, separated by `gap` blank columns.
+    fn render(font: &[(char, &str)], letters: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = letters
+            .chars()
+            .map(|letter| {
+                let (_, glyph) = font.iter().find(|(c, _)| *c == letter).unwrap();
+                glyph.lines().collect()
+            })
+            .collect();
+
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[y])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        let letters: String = FONT_6.iter().map(|(c, _)| c).collect();
+        assert_eq!(
+            recognize(&render(FONT_6, &letters, 1)).as_deref(),
+            Some(letters.as_str())
+        );
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let letters: String = FONT_10.iter().map(|(c, _)| c).collect();
+        assert_eq!(
+            recognize(&render(FONT_10, &letters, 2)).as_deref(),
+            Some(letters.as_str())
+        );
+    }
+
+    #[test]
+    fn reads_other_pixels_and_padding() {
+        let grid = render(FONT_6, "EZ", 1).replace('#', "█").replace('.', " ");
+        assert_eq!(recognize(&format!("\n{grid}\n\n")).as_deref(), Some("EZ"));
+    }
+
+    #[test]
+    fn reads_touching_letters() {
+        assert_eq!(recognize(&render(FONT_6, "YZ", 0)).as_deref(), Some("YZ"));
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(recognize("#\n#\n#"), None);
+        assert_eq!(recognize("##\n##\n##\n##\n##\n##"), None);
+        assert_eq!(recognize(""), None);
+    }
+}
@@ -0,0 +1,310 @@
+//! Machine-readable result records exchanged between solution bins and the multi-day runner.
+//!
+//! When a solution bin is invoked with `--results <path>`, every phase appends one JSON line to `<path>`.
+//! The runner reads these records back instead of parsing the human-readable output of the child.
+
+use std::{
+    collections::HashMap,
+    env, fmt, fs,
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::stats::{self, BenchStats};
+
+pub const RESULTS_FLAG: &str = "--results";
+
+/// The phase of a solution a record belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// The optional input parsing step that runs before both parts.
+    Parse,
+    Part(u8),
+    /// A single run that solves both parts. The answers are reported as untimed part records.
+    Combined,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => f.write_str("parse"),
+            Phase::Part(part) => write!(f, "part_{part}"),
+            Phase::Combined => f.write_str("combined"),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "combined" => Ok(Phase::Combined),
+            x => x
+                .strip_prefix("part_")
+                .and_then(|part| part.parse().ok())
+                .map(Phase::Part)
+                .ok_or(format!("unknown phase `{x}`.")),
+        }
+    }
+}
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer, or the parse phase completed.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+}
+
+impl fmt::Display for PartStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartStatus::Solved => f.write_str("solved"),
+            PartStatus::Unsolved => f.write_str("unsolved"),
+            PartStatus::Failed => f.write_str("failed"),
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "failed" => Ok(PartStatus::Failed),
+            x => Err(format!("unknown part status `{x}`.")),
+        }
+    }
+}
+
+/// Result of running a single phase of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub phase: Phase,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    pub duration_nanos: f64,
+    /// The duration as printed by the runner, e.g. `74.1µs`.
+    pub duration_str: String,
+    pub samples: u128,
+    /// Present if the part was benchmarked.
+    pub stats: Option<BenchStats>,
+}
+
+/// Returns the results path if the current process was invoked with `--results <path>`.
+pub fn results_path() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == RESULTS_FLAG)?;
+    args.get(index + 1).cloned()
+}
+
+/// Append a record to the results file as a single JSON line.
+pub fn write_record(path: impl AsRef<Path>, record: &PartRecord) -> Result<(), io::Error> {
+    let line = JsonValue::from(record)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    writeln!(file, "{line}")
+}
+
+/// Read all records from a results file. A missing file yields no records.
+pub fn read_records(path: impl AsRef<Path>) -> Result<Vec<PartRecord>, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(vec![]);
+    };
+
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("record is not valid JSON."))?;
+            PartRecord::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("phase".into(), JsonValue::String(value.phase.to_string()));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos),
+        );
+        map.insert(
+            "duration_str".into(),
+            JsonValue::String(value.duration_str.clone()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let phase = json
+            .get("phase")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.phase to be a string.")?
+            .parse()?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let duration_nanos = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.duration_nanos to be a number.")?;
+
+        let duration_str = match json.get("duration_str") {
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .ok_or("Expected record.duration_str to be a string.")?,
+            None => stats::format_nanos(duration_nanos),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartRecord {
+            phase,
+            status,
+            answer: answer.cloned(),
+            duration_nanos,
+            duration_str,
+            samples,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_records, write_record, PartRecord, PartStatus, Phase};
+    use crate::template::stats::BenchStats;
+    use std::{env, fs, process, time::Duration};
+
+    fn get_mock_record(part: u8, answer: Option<&str>) -> PartRecord {
+        PartRecord {
+            phase: Phase::Part(part),
+            status: if answer.is_some() {
+                PartStatus::Solved
+            } else {
+                PartStatus::Unsolved
+            },
+            answer: answer.map(String::from),
+            duration_nanos: 74130.0,
+            duration_str: "74.1µs".into(),
+            samples: 100,
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let path = env::temp_dir().join(format!("aoc-protocol-test-{}.jsonl", process::id()));
+        let _ = fs::remove_file(&path);
+
+        let records = vec![
+            get_mock_record(1, Some("2 (74.13ns @ 100000 samples)")),
+            get_mock_record(2, Some("#..#\n.##.")),
+            get_mock_record(2, None),
+            PartRecord {
+                stats: BenchStats::from_samples(&[Duration::from_nanos(10); 3]),
+                ..get_mock_record(1, Some("1"))
+            },
+            PartRecord {
+                phase: Phase::Parse,
+                ..get_mock_record(1, None)
+            },
+            PartRecord {
+                phase: Phase::Combined,
+                ..get_mock_record(1, None)
+            },
+        ];
+
+        for record in &records {
+            write_record(&path, record).unwrap();
+        }
+
+        let parsed = read_records(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(parsed, records);
+    }
+
+    #[test]
+    fn handles_missing_file() {
+        let path = env::temp_dir().join("aoc-protocol-test-missing.jsonl");
+        assert_eq!(read_records(path).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn formats_missing_durations() {
+        let json: tinyjson::JsonValue =
+            r#"{"phase":"part_1","status":"solved","answer":"1","duration_nanos":74130,"samples":1,"stats":null}"#
+                .parse()
+                .unwrap();
+        assert_eq!(PartRecord::try_from(&json).unwrap().duration_str, "74.1µs");
+    }
+}
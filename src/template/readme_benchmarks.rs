@@ -3,7 +3,7 @@
 use std::{fs, io};
 
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{layout, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -14,6 +14,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => f.write_str(e),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -25,11 +34,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -52,75 +56,164 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// The timings of the flat layout (`year` is `None`) or of a year directory.
+pub struct TimingGroup {
+    pub year: Option<Year>,
+    pub timings: Timings,
+    pub total_millis: f64,
+}
+
+impl TimingGroup {
+    fn new(year: Option<Year>, timings: Timings) -> Self {
+        let total_millis = timings.total_millis();
+        Self {
+            year,
+            timings,
+            total_millis,
+        }
+    }
+}
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+fn construct_group(lines: &mut Vec<String>, group: TimingGroup) {
+    // NOTE: the parse column is only shown if at least one day times its parse step separately.
+    let has_parse = group.timings.has_parse();
+
+    if has_parse {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    for timing in group.timings.data {
+        let path = layout::bin_path(group.year, timing.day);
+        let parse_column = if has_parse {
+            format!(" `{}` |", timing.parse.unwrap_or_else(|| "-".into()))
+        } else {
+            String::new()
+        };
+        // days that solve both parts at once show their combined time in the part 1 column.
+        let (part_1, part_2) = match timing.combined {
+            Some(combined) => (format!("`{combined}` (both parts)"), "_combined_".into()),
+            None => (
+                format!("`{}`", timing.part_1.unwrap_or_else(|| "-".into())),
+                format!("`{}`", timing.part_2.unwrap_or_else(|| "-".into())),
+            ),
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}](./{}) |{} {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse_column,
+            part_1,
+            part_2
         ));
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(format!("**Total: {:.2}ms**", group.total_millis));
+}
+
+fn construct_table(prefix: &str, groups: Vec<TimingGroup>) -> String {
+    let header = format!("{prefix} Benchmarks");
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
+
+    for group in groups {
+        // year directories get a sub-heading, the flat layout is listed first without one.
+        if let Some(year) = group.year {
+            lines.push(String::new());
+            lines.push(format!("{prefix}# {year}"));
+        }
+        lines.push(String::new());
+        construct_group(&mut lines, group);
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(s: &mut String, groups: Vec<TimingGroup>) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", groups);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the benchmarks table with the stored timings of the flat layout and of every year directory.
+pub fn update() -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+
+    let mut groups: Vec<TimingGroup> = vec![];
+
+    for year in std::iter::once(None).chain(layout::scoped_years().into_iter().map(Some)) {
+        let timings = Timings::read_from_file_for_year(year).map_err(Error::Parser)?;
+        if !timings.data.is_empty() {
+            groups.push(TimingGroup::new(year, timings));
+        }
+    }
+
+    update_content(&mut readme, groups)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, TimingGroup, MARKER};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        template::Year,
+    };
+
+    fn flat(timings: Timings) -> Vec<TimingGroup> {
+        vec![TimingGroup {
+            year: None,
+            timings,
+            total_millis: 190.0,
+        }]
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +224,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, flat(get_mock_timings())).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, flat(get_mock_timings())).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, flat(get_mock_timings())).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, flat(get_mock_timings())).unwrap();
+        update_content(&mut s, flat(get_mock_timings())).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +253,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, flat(get_mock_timings())).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +273,77 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some("5ms".into());
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, flat(timings)).unwrap();
+        let expected = [
+            "foo",
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_combined_days() {
+        let mut timings = get_mock_timings();
+        timings.data[1].combined = Some("35ms".into());
+        timings.data[1].part_1 = None;
+        timings.data[1].part_2 = None;
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, flat(timings)).unwrap();
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `35ms` (both parts) | _combined_ |"));
+    }
+
+    #[test]
+    fn format_benchmarks_per_year() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut groups = flat(get_mock_timings());
+        groups.push(TimingGroup {
+            year: Year::new(2024),
+            timings: Timings {
+                data: vec![get_mock_timings().data[0].clone()],
+            },
+            total_millis: 30.0,
+        });
+        update_content(&mut s, groups).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "### 2024",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2024/01.rs) | `10ms` | `20ms` |",
+            "",
+            "**Total: 30.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
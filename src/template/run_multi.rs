@@ -1,40 +1,220 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    answers::Answers,
+    protocol::{PartRecord, PartStatus, Phase},
+    runner::{self, RegisteredDay},
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Options that control how `run_multi` executes days.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Whether to run optimized builds. Days only run from the registry of this binary if it was built
+    /// with the same profile, otherwise they are built with the requested one.
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Number of days that run concurrently. Running more than one job runs every day in a separate process,
+    /// so that the output of a solution can be captured along with the day it belongs to.
+    pub jobs: usize,
+    /// Days running longer than this are killed. Setting a timeout runs every day in a separate process.
+    pub timeout: Option<Duration>,
+}
 
-    let mut need_space = false;
+/// How running a single day ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    Ok,
+    /// The day has not been scaffolded yet.
+    NotSolved,
+    /// At least one part did not produce its known answer.
+    Wrong,
+    Panicked,
+    TimedOut,
+    /// At least one part returned an error.
+    Errored,
+    /// The solution process exited with a non-zero status other than a panic.
+    Failed(Option<i32>),
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+impl DayStatus {
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            DayStatus::Wrong
+                | DayStatus::Panicked
+                | DayStatus::TimedOut
+                | DayStatus::Errored
+                | DayStatus::Failed(_)
+        )
+    }
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayStatus::Ok => f.write_str("ok"),
+            DayStatus::NotSolved => f.write_str("not solved"),
+            DayStatus::Wrong => f.write_str("wrong"),
+            DayStatus::Panicked => f.write_str("panicked"),
+            DayStatus::TimedOut => f.write_str("timed out"),
+            DayStatus::Errored => f.write_str("error"),
+            DayStatus::Failed(Some(code)) => write!(f, "failed (exit status {code})"),
+            DayStatus::Failed(None) => f.write_str("failed"),
+        }
+    }
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+/// A part whose answer differs from its known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub part: u8,
+    pub expected: String,
+    pub actual: Option<String>,
+}
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.actual {
+            Some(actual) => write!(
+                f,
+                "part {}: expected `{}`, got `{actual}`",
+                self.part, self.expected
+            ),
+            None => write!(
+                f,
+                "part {}: expected `{}`, got no answer",
+                self.part, self.expected
+            ),
+        }
+    }
+}
+
+/// Result records and status of a single day.
+#[derive(Clone, Debug)]
+pub struct DayResult {
+    pub day: Day,
+    pub status: DayStatus,
+    pub records: Vec<PartRecord>,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl DayResult {
+    /// Returns the answer a part produced, if any.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.records
+            .iter()
+            .find(|r| r.phase == Phase::Part(part))
+            .and_then(|r| r.answer.as_deref())
+    }
+
+    /// Compare the answers of this day against known answers, marking the day as wrong on mismatch.
+    fn check_answers(&mut self, answers: &Answers) {
+        for part in 1..=2 {
+            let Some(expected) = answers.get(self.day, part) else {
+                continue;
+            };
+
+            let actual = self.answer(part);
+
+            if actual != Some(expected) {
+                self.mismatches.push(Mismatch {
+                    part,
+                    expected: expected.into(),
+                    actual: actual.map(String::from),
+                });
             }
-        });
+        }
+
+        if !self.mismatches.is_empty()
+            && matches!(self.status, DayStatus::Ok | DayStatus::NotSolved)
+        {
+            self.status = DayStatus::Wrong;
+        }
+    }
+}
+
+/// Results of a `run_multi` invocation.
+#[derive(Clone, Debug)]
+pub struct RunSummary {
+    pub results: Vec<DayResult>,
+    /// Only present for timed runs.
+    pub timings: Option<Timings>,
+}
+
+impl RunSummary {
+    pub fn has_failures(&self) -> bool {
+        self.results.iter().any(|r| r.status.is_failure())
+    }
+}
+
+/// Run a set of days. Days contained in `registry` run in-process,
+/// all other days are invoked as separate solution binaries.
+/// Part answers are checked against `answers`, days with a mismatch are reported as wrong.
+///
+/// With `jobs > 1`, days run concurrently and each day's output is printed as one block in day order.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    registry: &[RegisteredDay],
+    answers: &Answers,
+    options: RunOptions,
+) -> RunSummary {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let run_day = |index: usize, day: Day| {
+        if index > 0 {
+            runner::write_output(format_args!("\n"));
+        }
+
+        runner::write_output(format_args!("{ANSI_BOLD}Day {day}{ANSI_RESET}\n------\n"));
+
+        let mut result = run_day(day, registry, options);
+        result.check_answers(answers);
+
+        match result.status {
+            DayStatus::NotSolved => runner::write_output(format_args!("Not solved.\n")),
+            DayStatus::TimedOut => runner::write_output(format_args!(
+                "Timed out after {:.1?}.\n",
+                options.timeout.unwrap_or_default()
+            )),
+            _ => {}
+        }
+
+        result
+    };
+
+    let results: Vec<DayResult> = if options.jobs > 1 {
+        run_parallel(&days, options.jobs, run_day)
+    } else {
+        days.iter()
+            .enumerate()
+            .map(|(index, day)| run_day(index, *day))
+            .collect()
+    };
+
+    let timings = if options.is_timed {
+        let timings: Vec<Timing> = results
+            .iter()
+            .filter(|result| !result.records.is_empty())
+            .map(|result| child_commands::parse_exec_time(&result.records, result.day))
+            .collect();
 
-    if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,14 +223,141 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    print_summary(&results);
+
+    RunSummary { results, timings }
+}
+
+fn run_day(day: Day, registry: &[RegisteredDay], options: RunOptions) -> DayResult {
+    // registered days are compiled with the profile of this binary, other profiles need their own build.
+    let is_current_profile = options.is_release != cfg!(debug_assertions);
+    let solution = RegisteredDay::find(registry, day).filter(|_| is_current_profile);
+
+    let (status, records) = match solution {
+        Some(solution) if options.timeout.is_none() && options.jobs <= 1 => {
+            match runner::run_in_process(solution, options.is_timed) {
+                Ok(records) => (DayStatus::Ok, records),
+                Err(records) => (DayStatus::Panicked, records),
+            }
+        }
+        _ => {
+            let run = if solution.is_some() {
+                child_commands::run_registered(day, options)
+            } else {
+                child_commands::run_solution(day, options)
+            };
+
+            match run {
+                Ok(Some(child)) => (child.status, child.records),
+                Ok(None) => (DayStatus::NotSolved, vec![]),
+                Err(e) => {
+                    eprintln!("Failed to run day {day}: {e}");
+                    (DayStatus::Failed(None), vec![])
+                }
+            }
+        }
+    };
+
+    // a solution that exits because a part returned an error is not a crash.
+    let has_failed_part = records.iter().any(|r| r.status == PartStatus::Failed);
+    let status = match status {
+        DayStatus::Ok | DayStatus::Failed(_) if has_failed_part => DayStatus::Errored,
+        status => status,
+    };
+
+    DayResult {
+        day,
+        status,
+        records,
+        mismatches: vec![],
+    }
+}
+
+/// Run days on `jobs` worker threads. The output of each day is captured and printed
+/// as soon as all days before it have been printed.
+fn run_parallel<F>(days: &[Day], jobs: usize, run_day: F) -> Vec<DayResult>
+where
+    F: Fn(usize, Day) -> DayResult + Sync,
+{
+    let next_index = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next_index = &next_index;
+            let run_day = &run_day;
+
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(index) else {
+                    break;
+                };
+                let result = runner::capture_output(|| run_day(index, *day));
+                if tx.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut results: Vec<Option<DayResult>> = vec![None; days.len()];
+        let mut pending: BTreeMap<usize, String> = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (index, (result, output)) in rx {
+            results[index] = Some(result);
+            pending.insert(index, output);
+
+            while let Some(output) = pending.remove(&next_to_print) {
+                runner::write_output(format_args!("{output}"));
+                next_to_print += 1;
+            }
+        }
+
+        results.into_iter().flatten().collect()
+    })
+}
+
+fn print_summary(results: &[DayResult]) {
+    let ran: Vec<&DayResult> = results
+        .iter()
+        .filter(|r| r.status != DayStatus::NotSolved)
+        .collect();
+
+    if ran.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("-------");
+
+    for result in ran {
+        println!("Day {}: {}", result.day, result.status);
+
+        for mismatch in &result.mismatches {
+            println!("  {mismatch}");
+        }
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
     IO(io::Error),
+    Protocol(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Protocol(message) => f.write_str(message),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -59,137 +366,274 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// Solutions can run in isolated processes, either as their own binary or via the registry of the main binary.
+/// This module encapsulates interaction with these processes, both invoking them as well as collecting their result records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{DayStatus, Error, RunOptions};
+    use crate::template::{
+        layout,
+        protocol::{self, PartRecord, PartStatus, Phase, RESULTS_FLAG},
+        runner, Day,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        env, fs,
+        io::Read,
+        path::{Path, PathBuf},
+        process::{self, Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// Exit code of a Rust process that panicked.
+    const PANIC_EXIT_CODE: i32 = 101;
+
+    /// Result of running a solution in a child process.
+    pub struct ChildRun {
+        pub status: DayStatus,
+        pub records: Vec<PartRecord>,
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day. Returns `None` if the day has not been scaffolded yet.
+    pub fn run_solution(day: Day, options: RunOptions) -> Result<Option<ChildRun>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        let year = layout::scoped_year();
+
+        if !Path::new(&layout::bin_path(year, day)).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin = layout::bin_name(year, day);
+        let executable = match build_solution(&bin, options.is_release)? {
+            Ok(executable) => executable,
+            Err(status) => {
+                return Ok(Some(ChildRun {
+                    status: DayStatus::Failed(status.code()),
+                    records: vec![],
+                }))
+            }
+        };
+
+        // run the built binary directly rather than via `cargo run`, so a timeout kills the solution itself.
+        run_child(Command::new(executable), vec![], day, options).map(Some)
+    }
+
+    /// Build the binary of a solution. Returns the path of the executable, or the exit status of a failed build.
+    fn build_solution(bin: &str, is_release: bool) -> Result<Result<PathBuf, ExitStatus>, Error> {
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+        ];
+        args.extend(["--bin", bin]);
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
-
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let stderr = if runner::is_capturing_output() {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        };
 
-        let mut cmd = Command::new("cargo")
+        let mut child = Command::new("cargo")
             .args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(stderr)
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let stdout = child.stdout.take().map(read_to_end);
+        let stderr = child.stderr.take().map(read_to_end);
+        let status = child.wait()?;
 
-        let mut output = vec![];
+        let messages = stdout
+            .map(|r| r.join().unwrap_or_default())
+            .unwrap_or_default();
+        if let Some(stderr) = stderr {
+            let output = stderr.join().unwrap_or_default();
+            runner::write_output(format_args!("{}", String::from_utf8_lossy(&output)));
+        }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        if !status.success() {
+            return Ok(Err(status));
+        }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        String::from_utf8_lossy(&messages)
+            .lines()
+            .find_map(|line| artifact_executable(line, bin))
+            .map(Ok)
+            .ok_or_else(|| {
+                Error::Protocol(format!("cargo did not report an executable for `{bin}`."))
+            })
+    }
+
+    /// The executable of a `compiler-artifact` message of `cargo build --message-format=json` for the bin `bin`.
+    fn artifact_executable(message: &str, bin: &str) -> Option<PathBuf> {
+        let json: JsonValue = message.parse().ok()?;
+        let message = json.get::<HashMap<String, JsonValue>>()?;
+
+        let reason = message.get("reason")?.get::<String>()?;
+        let target = message.get("target")?.get::<HashMap<String, JsonValue>>()?;
+        let name = target.get("name")?.get::<String>()?;
+        if reason != "compiler-artifact" || name != bin {
+            return None;
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        message
+            .get("executable")?
+            .get::<String>()
+            .map(PathBuf::from)
+    }
+
+    /// Run a solution that is registered in the current binary in a separate process of that binary.
+    pub fn run_registered(day: Day, options: RunOptions) -> Result<Option<ChildRun>, Error> {
+        let args = vec!["run-day".to_string(), day.to_string()];
+        run_child(Command::new(env::current_exe()?), args, day, options).map(Some)
+    }
+
+    fn run_child(
+        mut cmd: Command,
+        mut args: Vec<String>,
+        day: Day,
+        options: RunOptions,
+    ) -> Result<ChildRun, Error> {
+        let results_path =
+            env::temp_dir().join(format!("advent_of_code-{}-{day}.jsonl", process::id()));
+        let _ = fs::remove_file(&results_path);
+
+        args.push(RESULTS_FLAG.into());
+        args.push(results_path.to_string_lossy().to_string());
+
+        if options.is_timed {
+            // mirror `--time` flag to child invocations.
+            args.push("--time".into());
+        }
+
+        // the child prints its human-readable output directly, results are exchanged via the records file.
+        // when the runner captures output, the child's output is captured as well.
+        let is_capturing = runner::is_capturing_output();
+        let stdio = || {
+            if is_capturing {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            }
+        };
+
+        let mut child = cmd.args(&args).stdout(stdio()).stderr(stdio()).spawn()?;
+
+        let readers = [
+            child.stdout.take().map(read_to_end),
+            child.stderr.take().map(read_to_end),
+        ];
+
+        let exit_status = wait_with_timeout(&mut child, options.timeout)?;
+
+        for reader in readers.into_iter().flatten() {
+            let output = reader.join().unwrap_or_default();
+            runner::write_output(format_args!("{}", String::from_utf8_lossy(&output)));
+        }
+
+        let status = match exit_status {
+            None => DayStatus::TimedOut,
+            Some(exit_status) if exit_status.success() => DayStatus::Ok,
+            Some(exit_status) if exit_status.code() == Some(PANIC_EXIT_CODE) => DayStatus::Panicked,
+            Some(exit_status) => DayStatus::Failed(exit_status.code()),
+        };
+
+        let records = protocol::read_records(&results_path).map_err(Error::Protocol);
+        let _ = fs::remove_file(&results_path);
+
+        Ok(ChildRun {
+            status,
+            records: records?,
+        })
+    }
+
+    fn read_to_end(mut stream: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            let _ = stream.read_to_end(&mut buf);
+            buf
+        })
+    }
+
+    /// Wait for a child to exit. Kills the child and returns `None` if it does not exit within `timeout`.
+    fn wait_with_timeout(
+        child: &mut Child,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ExitStatus>, Error> {
+        let Some(timeout) = timeout else {
+            return Ok(Some(child.wait()?));
+        };
+
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                return Ok(None);
+            }
 
-        Ok(output)
+            thread::sleep(Duration::from_millis(10));
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn parse_exec_time(records: &[PartRecord], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
+            combined: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            combined_stats: None,
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        // NOTE: the part records of a combined run only carry answers, the time is on the combined record.
+        let is_combined = records.iter().any(|r| r.phase == Phase::Combined);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+        records
+            .iter()
+            .filter(|r| r.status == PartStatus::Solved)
+            .for_each(|r| {
+                match r.phase {
+                    Phase::Part(_) if is_combined => return,
+                    Phase::Combined => {
+                        timings.combined = Some(r.duration_str.clone());
+                        timings.combined_stats.clone_from(&r.stats);
+                    }
+                    Phase::Parse => {
+                        timings.parse = Some(r.duration_str.clone());
+                        timings.parse_stats.clone_from(&r.stats);
+                    }
+                    Phase::Part(1) => {
+                        timings.part_1 = Some(r.duration_str.clone());
+                        timings.part_1_stats.clone_from(&r.stats);
+                    }
+                    Phase::Part(2) => {
+                        timings.part_2 = Some(r.duration_str.clone());
+                        timings.part_2_stats.clone_from(&r.stats);
+                    }
+                    Phase::Part(_) => return,
                 }
 
-                timings.total_nanos += nanos;
+                timings.total_nanos += r.duration_nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        #[allow(clippy::double_ended_iterator_last)]
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -206,17 +650,33 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{artifact_executable, parse_exec_time};
 
         use crate::day;
+        use crate::template::protocol::{PartRecord, PartStatus, Phase};
+
+        fn record(part: u8, answer: Option<&str>, duration: (&str, f64)) -> PartRecord {
+            PartRecord {
+                phase: Phase::Part(part),
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+                answer: answer.map(String::from),
+                duration_nanos: duration.1,
+                duration_str: duration.0.into(),
+                samples: 100,
+                stats: None,
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(1, Some("0"), ("74.13ns", 74.13)),
+                    record(2, Some("10"), ("74.13ms", 74_130_000.0)),
                 ],
                 day!(1),
             );
@@ -229,9 +689,16 @@ pub mod child_commands {
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(
+                        1,
+                        Some("@ @ @ ( ) ms (2s @ 5 samples)"),
+                        ("2s", 2_000_000_000.0),
+                    ),
+                    record(
+                        2,
+                        Some("10s\n(100ms @ 1 samples)"),
+                        ("100ms", 100_000_000.0),
+                    ),
                 ],
                 day!(1),
             );
@@ -240,13 +707,51 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    PartRecord {
+                        phase: Phase::Parse,
+                        answer: None,
+                        ..record(1, Some(""), ("1.0µs", 1_000.0))
+                    },
+                    record(1, Some("1"), ("2.0µs", 2_000.0)),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 3000_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.part_1.unwrap(), "2.0µs");
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_combined_times() {
+            let res = parse_exec_time(
+                &[
+                    PartRecord {
+                        phase: Phase::Combined,
+                        answer: None,
+                        ..record(1, Some(""), ("5.0µs", 5_000.0))
+                    },
+                    record(1, Some("1"), ("0ns", 0.0)),
+                    record(2, Some("2"), ("0ns", 0.0)),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 5000_f64);
+            assert_eq!(res.combined.unwrap(), "5.0µs");
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(1, None, ("10ns", 10.0)),
+                    record(2, None, ("10ns", 10.0)),
                 ],
                 day!(1),
             );
@@ -254,5 +759,26 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn finds_built_executables() {
+            let message = r#"{"reason":"compiler-artifact","target":{"name":"01","kind":["bin"]},"executable":"/target/debug/01"}"#;
+            assert_eq!(
+                artifact_executable(message, "01"),
+                Some("/target/debug/01".into())
+            );
+            assert_eq!(artifact_executable(message, "02"), None);
+            assert_eq!(
+                artifact_executable(r#"{"reason":"build-finished","success":true}"#, "01"),
+                None
+            );
+            assert_eq!(
+                artifact_executable(
+                    r#"{"reason":"compiler-artifact","target":{"name":"01"},"executable":null}"#,
+                    "01"
+                ),
+                None
+            );
+        }
     }
 }
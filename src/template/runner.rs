@@ -1,35 +1,309 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
+use std::fmt::{self, Display};
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::Answers;
+use crate::template::protocol::{self, PartRecord, PartStatus, Phase};
+use crate::template::stats::{self, BenchStats};
+use crate::template::submissions::{Refusal, Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_client, layout, ocr, Answer, Day, Solution, Year, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// A solution that is compiled into the main binary and can be run in-process.
+#[derive(Clone, Copy, Debug)]
+pub struct RegisteredDay {
+    /// Set if the solution lives in a year directory.
+    pub year: Option<Year>,
+    pub day: Day,
+    /// Runs all parts of the solution against the day's input.
+    pub run: fn(),
+}
+
+impl RegisteredDay {
+    /// Find the solution of a day in the layout of the current year.
+    pub fn find(registry: &[RegisteredDay], day: Day) -> Option<&RegisteredDay> {
+        let year = layout::scoped_year();
+        registry
+            .iter()
+            .find(|solution| solution.day == day && solution.year == year)
+    }
+}
+
+/// State of a solution that is run in-process by the multi-day runner.
+struct InProcessRun {
+    is_timed: bool,
+    records: Vec<PartRecord>,
+}
+
+thread_local! {
+    static IN_PROCESS: RefCell<Option<InProcessRun>> = const { RefCell::new(None) };
+    static CAPTURED_OUTPUT: RefCell<Option<String>> = const { RefCell::new(None) };
+    static HAS_FAILED_PART: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Write runner output to stdout, or to the capture buffer of the current thread.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        write_output(format_args!($($arg)*))
+    };
+}
+
+/// Write transient progress output that is overwritten by the final line. Skipped while capturing.
+macro_rules! progress {
+    ($($arg:tt)*) => {
+        if !is_capturing_output() {
+            write_output(format_args!($($arg)*));
+        }
+    };
+}
+
+/// Run `func` while capturing all runner output of the current thread instead of printing it.
+pub fn capture_output<R>(func: impl FnOnce() -> R) -> (R, String) {
+    let previous = CAPTURED_OUTPUT.replace(Some(String::new()));
+    let result = func();
+    let output = CAPTURED_OUTPUT.replace(previous).unwrap_or_default();
+    (result, output)
+}
+
+pub fn is_capturing_output() -> bool {
+    CAPTURED_OUTPUT.with_borrow(Option::is_some)
+}
+
+pub fn write_output(args: fmt::Arguments) {
+    let is_captured = CAPTURED_OUTPUT.with_borrow_mut(|output| match output {
+        Some(output) => {
+            let _ = fmt::Write::write_fmt(output, args);
+            true
+        }
+        None => false,
+    });
+
+    if !is_captured {
+        let mut stdout = stdout();
+        let _ = stdout.write_fmt(args);
+        let _ = stdout.flush();
+    }
+}
+
+/// Run a registered solution in the current process and collect its result records.
+/// If the solution panics, the records of the parts that completed before the panic are returned as error.
+pub fn run_in_process(
+    solution: &RegisteredDay,
+    is_timed: bool,
+) -> Result<Vec<PartRecord>, Vec<PartRecord>> {
+    IN_PROCESS.set(Some(InProcessRun {
+        is_timed,
+        records: vec![],
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(solution.run));
+    let records = IN_PROCESS.take().map(|run| run.records).unwrap_or_default();
+
+    match result {
+        Ok(()) => Ok(records),
+        Err(_) => Err(records),
+    }
+}
+
+fn is_in_process() -> bool {
+    IN_PROCESS.with_borrow(Option::is_some)
+}
+
+fn is_timed() -> bool {
+    IN_PROCESS
+        .with_borrow(|run| run.as_ref().map(|run| run.is_timed))
+        .unwrap_or_else(|| env::args().any(|x| x == "--time"))
+}
+
+/// Run a solution part. Parts can return anything that converts into an [`Answer`].
+pub fn run_part<I: Copy, R: Into<Answer>>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (answer, duration, stats) = run_timed(
+        |input| func(input).into(),
+        input,
+        |answer| print_result(answer, &part_str, ""),
+    );
+
+    let duration_str = format_duration(&duration, stats.as_ref());
+    report_part(answer, day, part, &duration_str, &duration, stats);
+}
+
+/// Run the optional parse step of a solution and time it separately from the parts.
+/// If parsing fails, the error fails the solution like an error of a part, and `None` is returned so the parts are skipped.
+pub fn run_parse<P, E: Display>(func: impl Fn(&str) -> Result<P, E>, input: &str) -> Option<P> {
+    let (parsed, duration, stats) = run_timed(func, input, |_| progress!("Parse:"));
+    let duration_str = format_duration(&duration, stats.as_ref());
+
+    progress!("\r");
+
+    match parsed {
+        Ok(parsed) => {
+            out!("Parse:{duration_str}\n");
+            record_result(Phase::Parse, PartStatus::Solved, None, &duration, stats);
+            Some(parsed)
+        }
+        Err(e) => {
+            out!("Parse: {}{duration_str}\n", Answer::Error(e.to_string()));
+            HAS_FAILED_PART.set(true);
+            record_result(Phase::Parse, PartStatus::Failed, None, &duration, stats);
+            None
+        }
+    }
+}
+
+/// Adapt a parse step that cannot fail to [`run_parse`].
+pub fn infallible<P>(func: impl Fn(&str) -> P) -> impl Fn(&str) -> Result<P, Infallible> {
+    move |input| Ok(func(input))
+}
+
+/// Run a solution that solves both parts at once. The run is timed as a whole, the answers are
+/// printed, recorded and submitted per part.
+pub fn run_solve<I: Copy, A: Into<Answer>, B: Into<Answer>>(
+    func: impl Fn(I) -> (A, B),
+    input: I,
+    day: Day,
+) {
+    let (result, duration, stats) = run_timed(
+        |input| {
+            let (part_one, part_two) = func(input);
+            (part_one.into(), part_two.into())
+        },
+        input,
+        |_| progress!("Solve:"),
+    );
+
+    progress!("\r");
+    out!("Solve:{}\n", format_duration(&duration, stats.as_ref()));
+
+    let (part_one, part_two): (Answer, Answer) = result;
+
+    let status = match (part_status(&part_one), part_status(&part_two)) {
+        (PartStatus::Solved, PartStatus::Solved) => PartStatus::Solved,
+        (PartStatus::Failed, _) | (_, PartStatus::Failed) => PartStatus::Failed,
+        _ => PartStatus::Unsolved,
+    };
+
+    record_result(Phase::Combined, status, None, &duration, stats);
+
+    let duration_str = format!(" {ANSI_ITALIC}(solved together){ANSI_RESET}");
+    report_part(part_one, day, 1, &duration_str, &Duration::ZERO, None);
+    report_part(part_two, day, 2, &duration_str, &Duration::ZERO, None);
+}
+
+/// Print, record and possibly submit the final answer of a part.
+/// Grids that spell letters are read, see [`ocr::recognize`], and handled like text answers.
+fn report_part(
+    answer: Answer,
+    day: Day,
+    part: u8,
+    duration_str: &str,
+    duration: &Duration,
+    stats: Option<BenchStats>,
+) {
+    let (answer, grid) = match answer {
+        Answer::Grid(grid) => match ocr::recognize(&grid) {
+            Some(letters) => (Answer::Text(letters), Some(grid)),
+            None => (Answer::Grid(grid), None),
+        },
+        answer => (answer, None),
+    };
+
+    print_result(&answer, &format!("Part {part}"), duration_str);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Some(grid) = grid {
+        out!("{grid}\n");
+    }
+
+    let status = part_status(&answer);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if status == PartStatus::Failed {
+        HAS_FAILED_PART.set(true);
+    }
+
+    record_result(Phase::Part(part), status, answer.value(), duration, stats);
+    submit_result(&answer, day, part);
+}
+
+fn part_status(answer: &Answer) -> PartStatus {
+    match answer {
+        Answer::Error(_) => PartStatus::Failed,
+        answer if answer.is_solved() => PartStatus::Solved,
+        _ => PartStatus::Unsolved,
+    }
+}
+
+/// Exit with a non-zero status if the parse step or a part of the solution returned an error.
+/// Runs in-process report the failure through their result records instead.
+pub fn exit_on_error() {
+    if HAS_FAILED_PART.get() && !is_in_process() {
+        process::exit(1);
+    }
+}
+
+/// Run a [`Solution`]: its parse step, then both parts on the parsed input.
+pub fn run_solution<S: Solution>(input: &str, day: Day) {
+    if let Some(parsed) = run_parse(infallible(S::parse), input) {
+        run_part(S::part_one, &parsed, day, 1);
+        run_part(S::part_two, &parsed, day, 2);
+    }
+}
+
+/// Collect a result record if running in-process, or write it if the runner asked for one via `--results`.
+fn record_result(
+    phase: Phase,
+    status: PartStatus,
+    answer: Option<String>,
+    duration: &Duration,
+    stats: Option<BenchStats>,
+) {
+    let record = PartRecord {
+        phase,
+        status,
+        answer,
+        #[allow(clippy::cast_precision_loss)]
+        duration_nanos: duration.as_nanos() as f64,
+        duration_str: format!("{duration:.1?}"),
+        samples: stats.as_ref().map_or(1, |s| s.samples + s.outliers),
+        stats,
+    };
+
+    if is_in_process() {
+        IN_PROCESS.with_borrow_mut(|run| {
+            if let Some(run) = run {
+                run.records.push(record);
+            }
+        });
+        return;
+    }
+
+    let Some(path) = protocol::results_path() else {
+        return;
+    };
+
+    if let Err(e) = protocol::write_record(&path, &record) {
+        eprintln!("Failed to write result record to \"{path}\": {e}");
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When benched, the returned duration is the median of all samples after outliers have been removed.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<BenchStats>) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,24 +315,29 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed() {
+        let stats = bench(func, input, &base_time);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = Duration::from_nanos(stats.median_nanos.round() as u64);
+        (result, duration, Some(stats))
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None)
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
+    progress!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm up caches and branch predictors before taking samples.
+    let warmup_iterations = (bench_iterations / 10).max(1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input)));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -67,75 +346,64 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    BenchStats::from_samples(&timers).expect("at least one benchmark sample")
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(duration: &Duration, stats: Option<&BenchStats>) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(stats) => format!(
+            " ({duration:.1?} @ {} samples) {ANSI_ITALIC}[min {}, p95 {}, ±{:.1}%, {} outliers, {} confidence]{ANSI_RESET}",
+            stats.samples + stats.outliers,
+            stats::format_nanos(stats.min_nanos),
+            stats::format_nanos(stats.p95_nanos),
+            stats.relative_stddev() * 100.0,
+            stats.outliers,
+            stats.confidence(),
+        ),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(answer: &Answer, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+    let str = match answer {
+        Answer::Integer(_) | Answer::Text(_) => {
+            format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}")
         }
+        Answer::Grid(_) => format!("{part}: ▼ {duration_str}"),
+        Answer::Error(_) => format!("{part}: {answer}{duration_str}"),
+        Answer::Unsolved if is_intermediate_result => format!("{part}: ✖"),
+        Answer::Unsolved => format!("{part}: ✖             "),
+    };
+
+    if is_intermediate_result {
+        progress!("{str}");
+        return;
+    }
+
+    progress!("\r");
+    out!("{str}\n");
+
+    if let Answer::Grid(grid) = answer {
+        out!("{grid}\n");
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+///  2. the selected backend is usable, see [`aoc_client::check`].
+///  3. the submission log does not rule the answer out.
+///
+/// The verdict is appended to the submission log; a correct answer is also stored as known answer.
+fn submit_result(
+    answer: &Answer,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
+    if is_in_process() || !args.contains(&"--submit".into()) {
         return None;
     }
 
@@ -155,11 +423,73 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let Some(answer) = answer.value().filter(|_| answer.is_submittable()) else {
+        match answer {
+            Answer::Grid(_) => {
+                eprintln!("Not submitting: the letters of the grid could not be read.");
+            }
+            _ => eprintln!("Not submitting: part {part} has no answer."),
+        }
+        return None;
+    };
+
+    if let Err(e) = aoc_client::check() {
+        eprintln!(
+            "{} backend is not usable: {e}",
+            aoc_client::Backend::from_env()
+        );
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let (mut submissions, mut answers) =
+        match (Submissions::read_from_file(), Answers::read_from_file()) {
+            (Ok(submissions), Ok(answers)) => (submissions, answers),
+            (Err(e), _) | (_, Err(e)) => {
+                eprintln!("Not submitting: failed to read {e}");
+                return None;
+            }
+        };
+
+    if let Some(accepted) = answers.get(day, part) {
+        eprintln!(
+            "Not submitting: {}",
+            Refusal::AlreadyAccepted(accepted.into())
+        );
+        return None;
+    }
+
+    if let Err(refusal) = submissions.check(day, part, &answer) {
+        eprintln!("Not submitting: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let response = aoc_client::submit(day, part, &answer);
+
+    if let Err(e) = &response {
+        eprintln!("Failed to submit result: {e}");
+    }
+
+    let verdict = response
+        .as_ref()
+        .ok()
+        .and_then(|r| Verdict::from_response(r));
+
+    if let Some(verdict) = verdict {
+        submissions.push(day, part, &answer, verdict);
+
+        if let Err(e) = submissions.store_file() {
+            eprintln!("Failed to store submission log: {e}");
+        }
+
+        if verdict == Verdict::Correct {
+            answers.set(day, part, &answer);
+
+            if let Err(e) = answers.store_file() {
+                eprintln!("Failed to store answers: {e}");
+            }
+        }
+    }
+
+    Some(response)
 }
@@ -0,0 +1,182 @@
+//! Storage of the session cookie used to authenticate with the Advent of Code website.
+
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+const SESSION_FILE_NAME: &str = "adventofcode.session";
+
+/// Where the session cookie was loaded from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionSource {
+    Env,
+    File(PathBuf),
+}
+
+impl Display for SessionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionSource::Env => write!(f, "environment variable AOC_SESSION"),
+            SessionSource::File(path) => write!(f, "file \"{}\"", path.display()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    pub token: String,
+    pub source: SessionSource,
+}
+
+impl Session {
+    /// Returns the session cookie from `AOC_SESSION`, the config file, or the legacy dotfile used by aoc-cli.
+    pub fn load() -> Option<Self> {
+        if let Ok(token) = env::var("AOC_SESSION") {
+            return Some(Session {
+                token: token.trim().to_string(),
+                source: SessionSource::Env,
+            });
+        }
+
+        [config_path(), legacy_path()]
+            .into_iter()
+            .flatten()
+            .find_map(|path| {
+                let token = read_session_file(&path)?;
+                Some(Session {
+                    token,
+                    source: SessionSource::File(path),
+                })
+            })
+    }
+
+    /// The token with all but the first and last four characters hidden.
+    pub fn masked(&self) -> String {
+        let chars: Vec<char> = self.token.chars().collect();
+
+        if chars.len() <= 8 {
+            return "*".repeat(chars.len());
+        }
+
+        format!(
+            "{}…{} ({} characters)",
+            chars[..4].iter().collect::<String>(),
+            chars[chars.len() - 4..].iter().collect::<String>(),
+            chars.len()
+        )
+    }
+}
+
+/// Checks that a token looks like a session cookie: a hex string of 96 or 128 characters.
+pub fn validate(token: &str) -> Result<(), String> {
+    if token.is_empty() {
+        return Err("the session cookie is empty.".into());
+    }
+
+    if !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(
+            "the session cookie must only contain hex characters. Copy the value of the `session` cookie without its name."
+                .into(),
+        );
+    }
+
+    if token.len() != 96 && token.len() != 128 {
+        return Err(format!(
+            "the session cookie is {} characters long, expected 96 or 128.",
+            token.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Store a token in the config file, readable only by the current user.
+pub fn store(token: &str) -> Result<PathBuf, io::Error> {
+    let path = config_path().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "could not determine config directory",
+        )
+    })?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // the mode only applies to new files, restrict existing files as well.
+        if path.exists() {
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    let mut file = options.open(&path)?;
+    writeln!(file, "{token}")?;
+    Ok(path)
+}
+
+/// `<config_dir>/adventofcode.session`. aoc-cli reads the same file.
+pub fn config_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| home_dir().map(|home| home.join(".config")))?;
+
+    Some(config_dir.join(SESSION_FILE_NAME))
+}
+
+fn legacy_path() -> Option<PathBuf> {
+    home_dir().map(|home| home.join(format!(".{SESSION_FILE_NAME}")))
+}
+
+fn read_session_file(path: &Path) -> Option<String> {
+    let session = fs::read_to_string(path).ok()?;
+    let session = session.trim();
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{validate, Session, SessionSource};
+
+    #[test]
+    fn validates_tokens() {
+        assert!(validate(&"a1".repeat(64)).is_ok());
+        assert!(validate(&"F0".repeat(48)).is_ok());
+        assert!(validate("").is_err());
+        assert!(validate(&"ab".repeat(10)).is_err());
+        assert!(validate(&format!("session={}", "a1".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn masks_tokens() {
+        let session = |token: &str| Session {
+            token: token.into(),
+            source: SessionSource::Env,
+        };
+
+        assert_eq!(
+            session("0123456789ab").masked(),
+            "0123…89ab (12 characters)"
+        );
+        assert_eq!(session("0123").masked(), "****");
+    }
+}
@@ -0,0 +1,82 @@
+//! A trait-based alternative to free `part_one` / `part_two` functions.
+
+use crate::template::Answer;
+
+/// A solution that parses its input once and shares the result between both parts.
+///
+/// Register an implementation with `solution!(<day>, <type>)`. The runner times the parse step and
+/// both parts separately.
+///
+/// ```ignore
+/// advent_of_code::solution!(1, Day01);
+///
+/// struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Parsed = Vec<u64>;
+///     type PartOne = u64;
+///     type PartTwo = u64;
+///
+///     fn parse(input: &str) -> Self::Parsed {
+///         input.lines().map(|line| line.parse().unwrap()).collect()
+///     }
+///
+///     fn part_one(numbers: &Self::Parsed) -> Option<Self::PartOne> {
+///         Some(numbers.iter().sum())
+///     }
+///
+///     fn part_two(numbers: &Self::Parsed) -> Option<Self::PartTwo> {
+///         numbers.iter().max().copied()
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// The input after parsing, passed to both parts.
+    type Parsed;
+    type PartOne: Into<Answer>;
+    type PartTwo: Into<Answer>;
+
+    fn parse(input: &str) -> Self::Parsed;
+    fn part_one(parsed: &Self::Parsed) -> Option<Self::PartOne>;
+    fn part_two(parsed: &Self::Parsed) -> Option<Self::PartTwo>;
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Solution;
+    use crate::day;
+    use crate::template::runner::{capture_output, run_solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<u64>;
+        type PartOne = u64;
+        type PartTwo = String;
+
+        fn parse(input: &str) -> Self::Parsed {
+            input.lines().map(|line| line.parse().unwrap()).collect()
+        }
+
+        fn part_one(numbers: &Self::Parsed) -> Option<Self::PartOne> {
+            Some(numbers.iter().sum())
+        }
+
+        fn part_two(_: &Self::Parsed) -> Option<Self::PartTwo> {
+            None
+        }
+    }
+
+    #[test]
+    fn runs_parse_and_parts() {
+        let ((), output) = capture_output(|| run_solution::<Sum>("1\n2\n39\n", day!(1)));
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Parse:"));
+        assert!(lines[1].starts_with("Part 1: \x1b[1m42\x1b[0m"));
+        assert!(lines[2].starts_with("Part 2: ✖"));
+    }
+}
@@ -0,0 +1,234 @@
+//! Summary statistics for benchmark samples.
+
+use std::{collections::HashMap, fmt::Display, time::Duration};
+use tinyjson::JsonValue;
+
+/// Samples further than this many (scaled) median absolute deviations from the median are discarded.
+const OUTLIER_THRESHOLD: f64 = 3.0;
+
+/// Scales the MAD so that it estimates the standard deviation of normally distributed samples.
+const MAD_SCALE: f64 = 1.4826;
+
+/// Scales the mean absolute deviation, used when more than half of the samples are identical.
+const MEAN_AD_SCALE: f64 = 1.2533;
+
+/// How much the benchmark samples of a part agree with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Confidence {
+    High,
+    Medium,
+    Low,
+}
+
+impl Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Confidence::High => f.write_str("high"),
+            Confidence::Medium => f.write_str("medium"),
+            Confidence::Low => f.write_str("low"),
+        }
+    }
+}
+
+/// Statistics of a benchmark run, computed after outliers have been removed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub mean_nanos: f64,
+    pub median_nanos: f64,
+    pub min_nanos: f64,
+    pub p95_nanos: f64,
+    pub stddev_nanos: f64,
+    /// Number of samples kept after outlier rejection.
+    pub samples: u128,
+    /// Number of samples discarded as outliers.
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Computes statistics for a set of samples, returns `None` if no samples were taken.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        if nanos.is_empty() {
+            return None;
+        }
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let median = percentile(&nanos, 0.5);
+        let mut deviations: Vec<f64> = nanos.iter().map(|x| (x - median).abs()).collect();
+        deviations.sort_unstable_by(f64::total_cmp);
+        let mut mad = percentile(&deviations, 0.5) * MAD_SCALE;
+        if mad == 0.0 {
+            #[allow(clippy::cast_precision_loss)]
+            let mean_deviation = deviations.iter().sum::<f64>() / deviations.len() as f64;
+            mad = mean_deviation * MEAN_AD_SCALE;
+        }
+
+        let kept: Vec<f64> = if mad > 0.0 {
+            nanos
+                .iter()
+                .copied()
+                .filter(|x| (x - median).abs() <= OUTLIER_THRESHOLD * mad)
+                .collect()
+        } else {
+            nanos.clone()
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let variance = if kept.len() > 1 {
+            kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            mean_nanos: mean,
+            median_nanos: percentile(&kept, 0.5),
+            min_nanos: kept[0],
+            p95_nanos: percentile(&kept, 0.95),
+            stddev_nanos: variance.sqrt(),
+            samples: kept.len() as u128,
+            outliers: (nanos.len() - kept.len()) as u128,
+        })
+    }
+
+    /// Relative standard deviation of the kept samples.
+    pub fn relative_stddev(&self) -> f64 {
+        if self.mean_nanos > 0.0 {
+            self.stddev_nanos / self.mean_nanos
+        } else {
+            0.0
+        }
+    }
+
+    pub fn confidence(&self) -> Confidence {
+        match self.relative_stddev() {
+            x if self.samples < 10 || x > 0.15 => Confidence::Low,
+            x if x > 0.05 => Confidence::Medium,
+            _ => Confidence::High,
+        }
+    }
+}
+
+/// Linearly interpolated percentile of sorted values, `p` is in range 0 to 1.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    #[allow(clippy::cast_precision_loss)]
+    let weight = rank - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+/// Formats a nanosecond value the same way the runner prints durations.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            mean_nanos: number("mean_nanos")?,
+            median_nanos: number("median_nanos")?,
+            min_nanos: number("min_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+            stddev_nanos: number("stddev_nanos")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchStats, Confidence};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn computes_order_statistics() {
+        let stats = BenchStats::from_samples(&nanos(&[50, 10, 40, 20, 30])).unwrap();
+        assert_eq!(stats.median_nanos, 30.0);
+        assert_eq!(stats.min_nanos, 10.0);
+        assert_eq!(stats.mean_nanos, 30.0);
+        assert_eq!(stats.p95_nanos, 48.0);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let mut samples = vec![100; 20];
+        samples.extend([101, 99, 102, 98, 10_000]);
+        let stats = BenchStats::from_samples(&nanos(&samples)).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.samples, 24);
+        assert_eq!(stats.median_nanos, 100.0);
+        assert!(stats.mean_nanos < 101.0);
+        assert_eq!(stats.confidence(), Confidence::High);
+    }
+
+    #[test]
+    fn reports_low_confidence_for_noisy_samples() {
+        let samples: Vec<u64> = (1..=20).map(|x| x * 10).collect();
+        let stats = BenchStats::from_samples(&nanos(&samples)).unwrap();
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.confidence(), Confidence::Low);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let stats = BenchStats::from_samples(&nanos(&[50, 10, 40, 20, 30])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+}
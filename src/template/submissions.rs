@@ -0,0 +1,397 @@
+//! Local log of submitted answers and the verdicts returned by the server.
+//!
+//! The log is used to refuse submissions that are known to be wrong before they reach the server.
+
+use std::{collections::HashMap, fmt, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{layout, Day};
+
+static FILE_NAME: &str = "submissions.json";
+
+/// The verdict of the server for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about the direction.
+    Wrong,
+    /// The answer was not checked because the submission rate limit was hit.
+    Wait,
+}
+
+impl Verdict {
+    /// Extract the verdict from the response text of a submission.
+    pub fn from_response(response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if response.contains("answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if response.contains("answer is too low") {
+            Some(Verdict::TooLow)
+        } else if response.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if response.contains("You gave an answer too recently") {
+            Some(Verdict::Wait)
+        } else {
+            None
+        }
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::TooHigh => f.write_str("too_high"),
+            Verdict::TooLow => f.write_str("too_low"),
+            Verdict::Wrong => f.write_str("wrong"),
+            Verdict::Wait => f.write_str("wait"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "wait" => Ok(Verdict::Wait),
+            x => Err(format!("unknown verdict `{x}`.")),
+        }
+    }
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyAccepted(String),
+    KnownWrong,
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyAccepted(answer) => {
+                write!(f, "this part was already accepted with answer `{answer}`.")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Refusal::NotBelow(bound) => {
+                write!(
+                    f,
+                    "the answer must be lower than `{bound}`, which is too high."
+                )
+            }
+            Refusal::NotAbove(bound) => {
+                write!(
+                    f,
+                    "the answer must be higher than `{bound}`, which is too low."
+                )
+            }
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Represents all submissions made so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = layout::create_store_file(layout::scoped_year(), FILE_NAME)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file() -> Result<Self, String> {
+        layout::read_store_file(layout::scoped_year(), FILE_NAME, Submissions::try_from)
+    }
+
+    pub fn push(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+        });
+    }
+
+    /// The answer that was accepted for a part, if any.
+    pub fn accepted(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Check whether an answer may be submitted, based on previous verdicts for the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let submissions = || {
+            self.data
+                .iter()
+                .filter(move |s| s.day == day && s.part == part)
+        };
+
+        if let Some(accepted) = submissions().find(|s| s.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadyAccepted(accepted.answer.clone()));
+        }
+
+        if submissions().any(|s| s.answer == answer && s.verdict.is_wrong()) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        // bounds only apply to numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            submissions()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(upper) = bound(Verdict::TooHigh).min() {
+            if value >= upper {
+                return Err(Refusal::NotBelow(upper.to_string()));
+            }
+        }
+
+        if let Some(lower) = bound(Verdict::TooLow).max() {
+            if value <= lower {
+                return Err(Refusal::NotAbove(lower.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.day to be a string.")?;
+        let day =
+            Day::from_str(day).map_err(|e| format!("Invalid submission.day `{day}`: {e}."))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Refusal, Submissions, Verdict};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait."),
+            Some(Verdict::Wait)
+        );
+        assert_eq!(Verdict::from_response("unexpected"), None);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "abc", Verdict::Wrong);
+        submissions.push(day!(1), 1, "def", Verdict::Wait);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "abc"),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(submissions.check(day!(1), 1, "def"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "abc"), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "100", Verdict::TooHigh);
+        submissions.push(day!(1), 1, "80", Verdict::TooHigh);
+        submissions.push(day!(1), 1, "10", Verdict::TooLow);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "90"),
+            Err(Refusal::NotBelow("80".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "80"),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "-5"),
+            Err(Refusal::NotAbove("10".into()))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "#..#"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_accepted_parts() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "42", Verdict::Correct);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "43"),
+            Err(Refusal::AlreadyAccepted("42".into()))
+        );
+        assert_eq!(submissions.check(day!(1), 2, "43"), Ok(()));
+    }
+
+    #[test]
+    fn finds_accepted_answers() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "41", Verdict::TooLow);
+        submissions.push(day!(1), 1, "42", Verdict::Correct);
+        submissions.push(day!(1), 2, "7", Verdict::Wrong);
+
+        assert_eq!(submissions.accepted(day!(1), 1), Some("42"));
+        assert_eq!(submissions.accepted(day!(1), 2), None);
+        assert_eq!(submissions.accepted(day!(2), 1), None);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "100", Verdict::TooHigh);
+        submissions.push(day!(1), 2, "#..#\n.##.", Verdict::Correct);
+        let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
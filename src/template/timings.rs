@@ -1,16 +1,23 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{layout, stats::BenchStats, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Set if the day solves both parts in a single, combined run.
+    pub combined: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    pub combined_stats: Option<BenchStats>,
     pub total_nanos: f64,
 }
 
@@ -25,16 +32,18 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = layout::create_store_file(layout::scoped_year(), FILE_NAME)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    pub fn read_from_file() -> Result<Self, String> {
+        Self::read_from_file_for_year(layout::scoped_year())
+    }
+
+    /// Rehydrate the timings of a year directory, or of the flat layout if `year` is `None`.
+    pub fn read_from_file_for_year(year: Option<Year>) -> Result<Self, String> {
+        layout::read_store_file(year, FILE_NAME, Timings::try_from)
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,6 +60,7 @@ impl Timings {
             }
         }
 
+        #[allow(clippy::unnecessary_sort_by)]
         data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
         Timings { data }
     }
@@ -60,10 +70,15 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Whether any day has a separately timed parse step.
+    pub fn has_parse(&self) -> bool {
+        self.data.iter().any(|t| t.parse.is_some())
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| {
+            t.day == day && (t.combined.is_some() || (t.part_1.is_some() && t.part_2.is_some()))
+        })
     }
 }
 
@@ -133,6 +148,30 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
+        if let Some(combined) = &value.combined {
+            map.insert("combined".into(), JsonValue::String(combined.clone()));
+        }
+
+        if let Some(stats) = &value.parse_stats {
+            map.insert("parse_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_1_stats {
+            map.insert("part_1_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_2_stats {
+            map.insert("part_2_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.combined_stats {
+            map.insert("combined_stats".into(), JsonValue::from(stats));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -148,8 +187,8 @@ impl TryFrom<&JsonValue> for Timing {
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+            .ok_or("Expected timing.day to be a string.")?;
+        let day = Day::from_str(day).map_err(|e| format!("Invalid timing.day `{day}`: {e}."))?;
 
         let part_1 = json
             .get("part_1")
@@ -166,10 +205,43 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: parse timings and stats are optional to stay compatible with timings stored before they were introduced.
+        let parse = json.get("parse").and_then(|v| v.get::<String>()).cloned();
+        let combined = json
+            .get("combined")
+            .and_then(|v| v.get::<String>())
+            .cloned();
+
+        let parse_stats = json
+            .get("parse_stats")
+            .map(BenchStats::try_from)
+            .transpose()?;
+
+        let part_1_stats = json
+            .get("part_1_stats")
+            .map(BenchStats::try_from)
+            .transpose()?;
+
+        let part_2_stats = json
+            .get("part_2_stats")
+            .map(BenchStats::try_from)
+            .transpose()?;
+
+        let combined_stats = json
+            .get("combined_stats")
+            .map(BenchStats::try_from)
+            .transpose()?;
+
         Ok(Timing {
             day,
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            combined,
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            combined_stats,
             total_nanos,
         })
     }
@@ -188,20 +260,38 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -223,6 +313,17 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean_nanos": 1000000, "median_nanos": 1000000, "min_nanos": 900000, "p95_nanos": 1100000, "stddev_nanos": 10000, "samples": 98, "outliers": 2 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.min_nanos, 900_000_f64);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -230,6 +331,22 @@ mod tests {
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        fn handles_days_of_long_events() {
+            let json = r#"{ "data": [{ "day": "25", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].day, day!(25));
+        }
+
+        #[test]
+        fn reports_invalid_days() {
+            let json = r#"{ "data": [{ "day": "26", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            assert_eq!(
+                Timings::try_from(json).err(),
+                Some("Invalid timing.day `26`: expecting a day number between 1 and 25.".into())
+            );
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -279,13 +396,39 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
+        }
+
+        #[test]
+        fn handles_combined_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                    combined: Some("3ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
+                    total_nanos: 3_000_000_f64,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +436,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +456,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -331,8 +486,14 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -351,8 +512,14 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    combined: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    combined_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
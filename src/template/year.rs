@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{AllDays, Day, MAX_DAYS};
+
+/// The first year of advent of code.
+const FIRST_YEAR: u16 = 2015;
+
+/// Starting 2025, an event only has 12 puzzles.
+const SHORT_EVENTS_FROM: u16 = 2025;
+
+/// A year of advent of code, i.e. an event.
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2025).unwrap();
+/// assert_eq!(year.to_string(), "2025");
+/// assert_eq!(year.day_count(), 12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] if an event took place in that year, returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Returns the year of a solution that lives in a year directory, e.g. `src/bin/2024/01.rs`.
+    /// Used by [`crate::solution`] with the path of the solution file.
+    pub const fn from_source_path(path: &str) -> Option<Self> {
+        const fn is_separator(b: u8) -> bool {
+            b == b'/' || b == b'\\'
+        }
+
+        // the path ends in `/YYYY/DD.rs`.
+        let bytes = path.as_bytes();
+        if bytes.len() < 11 {
+            return None;
+        }
+
+        let start = bytes.len() - 11;
+        if !is_separator(bytes[start]) || !is_separator(bytes[start + 5]) {
+            return None;
+        }
+
+        let mut year = 0;
+        let mut i = start + 1;
+        while i < start + 5 {
+            if !bytes[i].is_ascii_digit() {
+                return None;
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        Self::new(year)
+    }
+
+    /// Returns the year configured via `AOC_YEAR`, if any.
+    pub fn current() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The number of puzzles of this event.
+    pub fn day_count(self) -> u8 {
+        if self.0 >= SHORT_EVENTS_FROM {
+            12
+        } else {
+            MAX_DAYS
+        }
+    }
+
+    /// Whether the event has a puzzle for `day`.
+    pub fn has_day(self, day: Day) -> bool {
+        day.into_inner() <= self.day_count()
+    }
+
+    /// An iterator over every day of this event.
+    pub fn days(self) -> AllDays {
+        AllDays::up_to(self.day_count())
+    }
+}
+
+/// The number of puzzles of the current event, or the maximum if no year is configured.
+pub fn day_count() -> u8 {
+    Year::current().map_or(MAX_DAYS, Year::day_count)
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+    use crate::day;
+
+    #[test]
+    fn counts_days_per_event() {
+        assert_eq!(Year::new(2015).unwrap().day_count(), 25);
+        assert_eq!(Year::new(2024).unwrap().day_count(), 25);
+        assert_eq!(Year::new(2025).unwrap().day_count(), 12);
+        assert_eq!(Year::new(2025).unwrap().days().count(), 12);
+        assert!(Year::new(2025).unwrap().has_day(day!(12)));
+        assert!(!Year::new(2025).unwrap().has_day(day!(13)));
+    }
+
+    #[test]
+    fn reads_year_from_source_path() {
+        assert_eq!(
+            Year::from_source_path("src/bin/2024/01.rs"),
+            Year::new(2024)
+        );
+        assert_eq!(
+            Year::from_source_path("C:\\aoc\\src\\bin\\2016\\25.rs"),
+            Year::new(2016)
+        );
+        assert_eq!(Year::from_source_path("src/bin/01.rs"), None);
+        assert_eq!(Year::from_source_path("01.rs"), None);
+        assert_eq!(Year::from_source_path("src/bin/abcd/01.rs"), None);
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().ok(), Year::new(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("abc".parse::<Year>().is_err());
+    }
+}